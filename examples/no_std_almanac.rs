@@ -0,0 +1,15 @@
+//! Compile check that the parsers are usable from a `no_std` + `alloc` crate.
+//!
+//! Build with `cargo build --no-default-features --examples` to verify the library itself
+//! compiles without `std`.
+#![no_std]
+
+extern crate alloc;
+
+use advent_of_code_2023::almanac::{Almanac, AlmanacParseError};
+use core::str::FromStr;
+
+pub fn lowest_location(input: &str) -> Result<Option<usize>, AlmanacParseError> {
+    let almanac = Almanac::from_str(input)?;
+    Ok(almanac.get_seed_locations().into_iter().min())
+}
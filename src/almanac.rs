@@ -1,9 +1,17 @@
-use log::{debug, trace};
-use std::cmp::min;
-use std::ops::Range;
-use std::str::FromStr;
+use crate::parse_numbers;
+use crate::span;
+use crate::span::Span;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::min;
+use core::ops::Range;
+use core::str::FromStr;
+use log::{debug, info, trace};
 use thiserror::Error;
 
+#[derive(Debug)]
 pub struct Almanac {
     seeds: Vec<usize>,
     seed_ranges: Vec<Range<usize>>,
@@ -16,7 +24,7 @@ pub struct Almanac {
     humidity_to_location_map: AlmanacMap,
 }
 
-#[derive(Default)]
+#[derive(Debug, Default)]
 pub struct AlmanacMap {
     values: Vec<RangeMap>,
 }
@@ -67,14 +75,8 @@ impl FromStr for Almanac {
         if !seed_line.starts_with("seeds: ") {
             return Err(AlmanacParseError::MissingSeeds);
         }
-        let seeds = seed_line[7..]
-            .split_whitespace()
-            .map(|n| {
-                n.trim()
-                    .parse::<usize>()
-                    .map_err(|_| AlmanacParseError::InvalidSeed(n.to_string()))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let seeds = parse_numbers::<usize>(&seed_line[7..])
+            .map_err(|e| AlmanacParseError::InvalidSeed(e.0))?;
 
         let seed_ranges = get_seed_ranges(&seeds)?;
 
@@ -138,6 +140,34 @@ impl Almanac {
         self.humidity_to_location_map.map(humidity)
     }
 
+    pub fn location_to_humidity(&self, location: usize) -> usize {
+        self.humidity_to_location_map.reverse_map(location)
+    }
+
+    pub fn humidity_to_temperature(&self, humidity: usize) -> usize {
+        self.temperature_to_humidity_map.reverse_map(humidity)
+    }
+
+    pub fn temperature_to_light(&self, temperature: usize) -> usize {
+        self.light_to_temperature_map.reverse_map(temperature)
+    }
+
+    pub fn light_to_water(&self, light: usize) -> usize {
+        self.water_to_light_map.reverse_map(light)
+    }
+
+    pub fn water_to_fertilizer(&self, water: usize) -> usize {
+        self.fertilizer_to_water_map.reverse_map(water)
+    }
+
+    pub fn fertilizer_to_soil(&self, fertilizer: usize) -> usize {
+        self.soil_to_fertilizer_map.reverse_map(fertilizer)
+    }
+
+    pub fn soil_to_seed(&self, soil: usize) -> usize {
+        self.seed_to_soil_map.reverse_map(soil)
+    }
+
     pub fn seed_to_soil_ranges(&self, seed: &Range<usize>) -> Vec<Range<usize>> {
         self.seed_to_soil_map.map_ranges(seed)
     }
@@ -176,6 +206,36 @@ impl Almanac {
         self.humidity_to_location(humidity)
     }
 
+    /// Each stage `seed` passes through on its way to a location, labelled by category.
+    pub fn trace(&self, seed: usize) -> Vec<(&'static str, usize)> {
+        let maps = [
+            ("soil", &self.seed_to_soil_map),
+            ("fertilizer", &self.soil_to_fertilizer_map),
+            ("water", &self.fertilizer_to_water_map),
+            ("light", &self.water_to_light_map),
+            ("temperature", &self.light_to_temperature_map),
+            ("humidity", &self.temperature_to_humidity_map),
+            ("location", &self.humidity_to_location_map),
+        ];
+        let mut trace = vec![("seed", seed)];
+        for (label, map) in maps {
+            let (_, value) = trace[trace.len() - 1];
+            trace.push((label, map.map(value)));
+        }
+        trace
+    }
+
+    /// Composes all seven maps into one that takes a seed straight to its location.
+    pub fn flatten(&self) -> AlmanacMap {
+        self.seed_to_soil_map
+            .compose(&self.soil_to_fertilizer_map)
+            .compose(&self.fertilizer_to_water_map)
+            .compose(&self.water_to_light_map)
+            .compose(&self.light_to_temperature_map)
+            .compose(&self.temperature_to_humidity_map)
+            .compose(&self.humidity_to_location_map)
+    }
+
     pub fn seed_range_to_location_ranges(&self, seed_range: &Range<usize>) -> Vec<Range<usize>> {
         debug!("seed range: {:?}", seed_range);
         let soil_ranges = self.seed_to_soil_ranges(seed_range);
@@ -220,19 +280,72 @@ impl Almanac {
             .collect()
     }
 
+    pub fn seed_of_min_location(&self) -> Option<usize> {
+        self.seeds
+            .iter()
+            .copied()
+            .min_by_key(|seed| self.seed_to_location(*seed))
+    }
+
+    pub fn seed_of_min_location_in_ranges(&self) -> Option<usize> {
+        // Each mapping stage splits a range into consecutive pieces in order, so the location
+        // ranges for a seed range partition it in order and we can recover the starting seed.
+        self.seed_ranges
+            .iter()
+            .flat_map(|seed_range| {
+                let mut seed = seed_range.start;
+                self.seed_range_to_location_ranges(seed_range)
+                    .into_iter()
+                    .map(move |location_range| {
+                        let start = seed;
+                        seed += location_range.length();
+                        (location_range.start, start)
+                    })
+            })
+            .min()
+            .map(|(_, seed)| seed)
+    }
+
+    /// Total number of seeds covered by the seed ranges.
+    pub fn seed_count(&self) -> usize {
+        self.seed_ranges.iter().map(|range| range.length()).sum()
+    }
+
     pub fn iter_all_seeds(&self) -> impl Iterator<Item = usize> + '_ {
         self.seed_ranges.iter().flat_map(|range| range.clone())
     }
 
+    pub fn seed_chunks(&self, chunk: usize) -> impl Iterator<Item = Range<usize>> + '_ {
+        let chunk = chunk.max(1);
+        self.seed_ranges.iter().flat_map(move |range| {
+            range
+                .clone()
+                .step_by(chunk)
+                .map(move |start| start..min(start + chunk, range.end))
+        })
+    }
+
     pub fn iter_all_seed_locations(&self) -> impl Iterator<Item = usize> + '_ {
         self.iter_all_seeds()
             .map(|seed| self.seed_to_location(seed))
     }
 
+    /// The seed ranges with overlapping ranges merged, so no seed is processed twice.
+    pub fn normalized_seed_ranges(&self) -> Vec<Range<usize>> {
+        span::merge(self.seed_ranges.iter().cloned())
+    }
+
     pub fn get_seed_location_ranges(&self) -> Vec<Range<usize>> {
-        self.seed_ranges
+        let seed_ranges = self.normalized_seed_ranges();
+        let total = seed_ranges.len();
+        seed_ranges
             .iter()
-            .flat_map(|seed_range| self.seed_range_to_location_ranges(seed_range))
+            .enumerate()
+            .flat_map(|(i, seed_range)| {
+                let location_ranges = self.seed_range_to_location_ranges(seed_range);
+                info!("processed {}/{} seed ranges", i + 1, total);
+                location_ranges
+            })
             .collect()
     }
 }
@@ -242,12 +355,82 @@ impl AlmanacMap {
         values.sort_by_key(|range_map| range_map.source_start);
         AlmanacMap { values }
     }
+
+    /// The map's ranges, ordered by source start.
+    pub fn iter(&self) -> impl Iterator<Item = &RangeMap> {
+        self.values.iter()
+    }
+
+    /// A single map equivalent to applying this map and then `next`.
+    pub fn compose(&self, next: &AlmanacMap) -> AlmanacMap {
+        let mut values = Vec::new();
+        // `map_ranges` splits its input into consecutive pieces without changing their lengths,
+        // so each output piece lines up with the next stretch of this map's sources.
+        for range_map in &self.values {
+            let mut source_start = range_map.source_start;
+            for mapped in next.map_ranges(&range_map.range_out()) {
+                let range_length = mapped.length();
+                if range_length > 0 {
+                    values.push(RangeMap {
+                        destination_start: mapped.start,
+                        source_start,
+                        range_length,
+                    });
+                }
+                source_start += range_length;
+            }
+        }
+        // Values this map passes through unchanged are left for `next` to map.
+        for range_map in &next.values {
+            for gap in self.gaps_within(&range_map.range_in()) {
+                values.push(RangeMap {
+                    destination_start: range_map.destination_start
+                        + (gap.start - range_map.source_start),
+                    source_start: gap.start,
+                    range_length: gap.length(),
+                });
+            }
+        }
+        AlmanacMap::new(values)
+    }
+
+    /// Splits `domain` into the source ranges this map explicitly maps and those it passes
+    /// through unchanged, each in ascending order.
+    pub fn coverage(&self, domain: &Range<usize>) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+        let mapped = self
+            .values
+            .iter()
+            .filter_map(|range_map| range_map.range_in().intersection(domain))
+            .filter(|range| !range.is_empty())
+            .collect();
+        (mapped, self.gaps_within(domain))
+    }
+
+    /// The parts of `range` that no range in this map covers.
+    fn gaps_within(&self, range: &Range<usize>) -> Vec<Range<usize>> {
+        let mut gaps = Vec::new();
+        let mut pos = range.start;
+        for range_in in self.values.iter().map(RangeMap::range_in) {
+            if range_in.start >= range.end {
+                break;
+            }
+            if range_in.start > pos {
+                gaps.push(pos..range_in.start);
+            }
+            pos = pos.max(range_in.end);
+        }
+        if pos < range.end {
+            gaps.push(pos..range.end);
+        }
+        gaps
+    }
+
     fn from_lines<'a>(
         lines: &mut impl Iterator<Item = &'a str>,
         map_prefix: &str,
     ) -> Result<Self, AlmanacParseError> {
         let header_line = lines
-            .next()
+            .find(|line| !line.trim().is_empty())
             .ok_or_else(|| AlmanacParseError::MissingHeaderLine(map_prefix.to_string()))?;
 
         if header_line.trim() != format!("{} map:", map_prefix) {
@@ -256,7 +439,7 @@ impl AlmanacMap {
 
         let mut values = vec![];
         for line in lines {
-            if line.is_empty() {
+            if line.trim().is_empty() {
                 break;
             }
             let range_map = line.parse::<RangeMap>()?;
@@ -274,14 +457,52 @@ impl AlmanacMap {
         value
     }
 
+    /// Finds an input that maps to `value`. Outputs of a mapped range are preferred over the
+    /// identity mapping, so an unmapped input that collides with a mapped output is not returned.
+    pub fn reverse_map(&self, value: usize) -> usize {
+        for range_map in &self.values {
+            if let Some(source_value) = range_map.reverse_map(value) {
+                return source_value;
+            }
+        }
+        value
+    }
+
     fn find_next_range_map(&self, value: usize) -> Option<&RangeMap> {
         self.values.iter().find(|range_map| {
             range_map.source_start >= value || range_map.range_in().contains(&value)
         })
     }
 
+    pub fn map_single_range(&self, range: &Range<usize>) -> Option<Range<usize>> {
+        match self.find_next_range_map(range.start) {
+            Some(range_map) => {
+                let range_in = range_map.range_in();
+                if range_in.contains(&range.start) {
+                    if range.end <= range_in.end {
+                        let start = range_map.map(range.start)?;
+                        Some(start..start + range.length())
+                    } else {
+                        None
+                    }
+                } else if range.end <= range_in.start {
+                    Some(range.clone())
+                } else {
+                    None
+                }
+            }
+            None => Some(range.clone()),
+        }
+    }
+
     pub fn map_ranges(&self, range: &Range<usize>) -> Vec<Range<usize>> {
         trace!("map_ranges: {:?}", range);
+        if !range.is_empty() {
+            if let Some(mapped) = self.map_single_range(range) {
+                return vec![mapped];
+            }
+        }
+
         let mut result = Vec::new();
 
         let mut pos = range.start;
@@ -300,8 +521,8 @@ impl AlmanacMap {
                 } else {
                     trace!("range_in does not contain {:?}", pos);
                     let start = pos;
-                    pos = range_in.start;
-                    start..range_in.start
+                    pos = min(range_in.start, range.end);
+                    start..pos
                 };
                 result.push(r);
             } else {
@@ -319,15 +540,15 @@ impl FromStr for RangeMap {
     type Err = AlmanacParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let values = s
-            .split_whitespace()
-            .map(|n| {
-                n.trim()
-                    .parse::<usize>()
-                    .map_err(|_| AlmanacParseError::InvalidValueInRange(n.to_string()))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        if values.len() != 3 {
+        let values =
+            parse_numbers::<usize>(s).map_err(|e| AlmanacParseError::InvalidValueInRange(e.0))?;
+        // A zero-length range can never map anything, so it is almost certainly a typo. Ranges
+        // must also end within `usize` for `map` and `range_in` to be able to add the length.
+        if values.len() != 3
+            || values[2] == 0
+            || values[0].checked_add(values[2]).is_none()
+            || values[1].checked_add(values[2]).is_none()
+        {
             return Err(AlmanacParseError::InvalidRange(s.to_string()));
         }
 
@@ -353,6 +574,26 @@ impl RangeMap {
         Some(self.destination_start + (value - self.source_start))
     }
 
+    pub fn reverse_map(&self, value: usize) -> Option<usize> {
+        if self.range_out().contains(&value) {
+            Some(self.source_start + (value - self.destination_start))
+        } else {
+            None
+        }
+    }
+
+    pub fn destination_start(&self) -> usize {
+        self.destination_start
+    }
+
+    pub fn source_start(&self) -> usize {
+        self.source_start
+    }
+
+    pub fn range_length(&self) -> usize {
+        self.range_length
+    }
+
     pub fn range_in(&self) -> Range<usize> {
         self.source_start..(self.source_start + self.range_length)
     }
@@ -360,19 +601,37 @@ impl RangeMap {
     pub fn range_out(&self) -> Range<usize> {
         self.destination_start..(self.destination_start + self.range_length)
     }
+
+    /// Splits `range` against this map, returning the mapped output for the overlapping part and
+    /// the unmapped leftovers (up to one piece either side), or `None` if they don't overlap.
+    pub fn intersect(&self, range: &Range<usize>) -> Option<(Range<usize>, Vec<Range<usize>>)> {
+        let range_in = self.range_in();
+        let overlap = range_in.intersection(range)?;
+        let start = self.destination_start + (overlap.start - self.source_start);
+        let mapped = start..start + overlap.length();
+        let leftover = [range.start..overlap.start, overlap.end..range.end]
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect();
+        Some((mapped, leftover))
+    }
 }
 
 fn get_seed_ranges(seeds: &[usize]) -> Result<Vec<Range<usize>>, AlmanacParseError> {
-    if seeds.len() % 2 != 0 {
+    if !seeds.len().is_multiple_of(2) {
         return Err(AlmanacParseError::InsufficientSeedNumbers);
     }
 
-    let seed_ranges = seeds
+    seeds
         .chunks_exact(2)
-        .map(|chunk| chunk[0]..(chunk[0] + chunk[1]))
-        .collect();
-
-    Ok(seed_ranges)
+        .map(|chunk| match chunk[0].checked_add(chunk[1]) {
+            Some(end) => Ok(chunk[0]..end),
+            None => Err(AlmanacParseError::InvalidSeed(format!(
+                "{} {}",
+                chunk[0], chunk[1]
+            ))),
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -380,10 +639,13 @@ mod tests {
     use super::*;
     use crate::io::Source;
 
-    fn get_example_almanac() -> Almanac {
+    fn get_example_input() -> String {
         let input = Source::try_from("inputs/day-5-example.txt").unwrap();
-        let input = input.read_string().unwrap();
-        Almanac::from_str(&input).unwrap()
+        input.read_string().unwrap()
+    }
+
+    fn get_example_almanac() -> Almanac {
+        Almanac::from_str(&get_example_input()).unwrap()
     }
 
     #[test]
@@ -416,6 +678,41 @@ mod tests {
         assert_eq!(result.range_length, 3);
     }
 
+    #[test]
+    fn test_parse_zero_length_range_map() {
+        let result = "1 2 0".parse::<RangeMap>();
+        assert_eq!(
+            result,
+            Err(AlmanacParseError::InvalidRange("1 2 0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_overflowing_seed_range() {
+        let input = get_example_input().replacen(
+            "seeds: 79 14 55 13",
+            &format!("seeds: 79 14 {} 5", usize::MAX),
+            1,
+        );
+        assert_eq!(
+            input.parse::<Almanac>().unwrap_err(),
+            AlmanacParseError::InvalidSeed(format!("{} 5", usize::MAX))
+        );
+    }
+
+    #[test]
+    fn test_parse_overflowing_range_map() {
+        for range in [format!("{} 0 2", usize::MAX), format!("0 {} 2", usize::MAX)] {
+            assert_eq!(
+                range.parse::<RangeMap>(),
+                Err(AlmanacParseError::InvalidRange(range.clone()))
+            );
+        }
+        assert!(format!("0 {} 1", usize::MAX - 1)
+            .parse::<RangeMap>()
+            .is_ok());
+    }
+
     #[test]
     fn test_example_almanac_seed_to_soil_mapping() {
         let almanac = get_example_almanac();
@@ -425,6 +722,145 @@ mod tests {
         assert_eq!(almanac.seed_to_soil(13), 13);
     }
 
+    fn assert_map_ranges_matches_points(map: &AlmanacMap, name: &str) {
+        let ranges = [
+            0..5,
+            10..11,
+            25..50,
+            15..65,
+            45..60,
+            50..100,
+            90..110,
+            0..120,
+            60..60,
+            97..99,
+        ];
+        for range in ranges {
+            let expected = span::merge(range.clone().map(|value| {
+                let mapped = map.map(value);
+                mapped..mapped + 1
+            }));
+            let actual = span::merge(map.map_ranges(&range));
+            assert_eq!(actual, expected, "{} map of {:?}", name, range);
+        }
+    }
+
+    #[test]
+    fn test_example_almanac_map_ranges_match_points() {
+        let almanac = get_example_almanac();
+        let maps = [
+            (&almanac.seed_to_soil_map, "seed-to-soil"),
+            (&almanac.soil_to_fertilizer_map, "soil-to-fertilizer"),
+            (&almanac.fertilizer_to_water_map, "fertilizer-to-water"),
+            (&almanac.water_to_light_map, "water-to-light"),
+            (&almanac.light_to_temperature_map, "light-to-temperature"),
+            (
+                &almanac.temperature_to_humidity_map,
+                "temperature-to-humidity",
+            ),
+            (&almanac.humidity_to_location_map, "humidity-to-location"),
+        ];
+        for (map, name) in maps {
+            assert_map_ranges_matches_points(map, name);
+        }
+    }
+
+    #[test]
+    fn test_map_ranges_with_gaps_match_points() {
+        // The example maps are all contiguous, so also cover ranges ending in a gap between maps.
+        let map = AlmanacMap::new(vec![
+            "200 60 10".parse().unwrap(),
+            "100 20 20".parse().unwrap(),
+        ]);
+        assert_map_ranges_matches_points(&map, "gapped");
+    }
+
+    #[test]
+    fn test_example_seed_to_soil_coverage() {
+        let almanac = get_example_almanac();
+        let (mapped, identity) = almanac.seed_to_soil_map.coverage(&(0..100));
+        assert_eq!(mapped, vec![50..98, 98..100]);
+        assert_eq!(identity, vec![0..50]);
+
+        let (mapped, identity) = almanac.seed_to_soil_map.coverage(&(40..60));
+        assert_eq!(mapped, vec![50..60]);
+        assert_eq!(identity, vec![40..50]);
+
+        let (mapped, identity) = almanac.seed_to_soil_map.coverage(&(99..120));
+        assert_eq!(mapped, vec![99..100]);
+        assert_eq!(identity, vec![100..120]);
+    }
+
+    #[test]
+    fn test_example_almanac_trace() {
+        let almanac = get_example_almanac();
+        assert_eq!(
+            almanac.trace(79),
+            vec![
+                ("seed", 79),
+                ("soil", 81),
+                ("fertilizer", 81),
+                ("water", 81),
+                ("light", 74),
+                ("temperature", 78),
+                ("humidity", 78),
+                ("location", 82),
+            ]
+        );
+        for seed in 0..120 {
+            let trace = almanac.trace(seed);
+            assert_eq!(
+                trace.last(),
+                Some(&("location", almanac.seed_to_location(seed)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_example_almanac_flatten() {
+        let almanac = get_example_almanac();
+        let flattened = almanac.flatten();
+        for seed in almanac.seeds.iter().copied().chain(0..120) {
+            assert_eq!(
+                flattened.map(seed),
+                almanac.seed_to_location(seed),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_example_almanac_map_iter() {
+        let almanac = get_example_almanac();
+        let ranges = almanac
+            .seed_to_soil_map
+            .iter()
+            .map(RangeMap::range_in)
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![50..98, 98..100]);
+    }
+
+    #[test]
+    fn test_example_almanac_reverse_layers() {
+        let almanac = get_example_almanac();
+        let location = almanac.seed_to_location(79);
+        assert_eq!(location, 82);
+        let humidity = almanac.location_to_humidity(location);
+        assert_eq!(humidity, 78);
+        let temperature = almanac.humidity_to_temperature(humidity);
+        assert_eq!(temperature, 78);
+        let light = almanac.temperature_to_light(temperature);
+        assert_eq!(light, 74);
+        let water = almanac.light_to_water(light);
+        assert_eq!(water, 81);
+        let fertilizer = almanac.water_to_fertilizer(water);
+        assert_eq!(fertilizer, 81);
+        let soil = almanac.fertilizer_to_soil(fertilizer);
+        assert_eq!(soil, 81);
+        assert_eq!(almanac.soil_to_seed(soil), 79);
+    }
+
     #[test]
     fn test_example_almanac_lowest_location() {
         let almanac = get_example_almanac();
@@ -461,6 +897,34 @@ mod tests {
         assert_eq!(lowest_location, Some(46));
     }
 
+    #[test]
+    fn test_seed_location_ranges_logs_progress() {
+        crate::test_log::init();
+        let almanac = get_example_almanac();
+        almanac.get_seed_location_ranges();
+        let messages = crate::test_log::captured_messages();
+        assert!(messages.contains(&"processed 1/2 seed ranges".to_string()));
+        assert!(messages.contains(&"processed 2/2 seed ranges".to_string()));
+    }
+
+    #[test]
+    fn test_normalized_seed_ranges_merges_overlaps() {
+        let input =
+            get_example_input().replacen("seeds: 79 14 55 13", "seeds: 79 14 85 10 55 13", 1);
+        let almanac = input.parse::<Almanac>().unwrap();
+        let seed_ranges = almanac.normalized_seed_ranges();
+        assert_eq!(seed_ranges.len(), 2);
+        assert_eq!(seed_ranges[0], 55..68);
+        assert_eq!(seed_ranges[1], 79..95);
+        let lowest_location = almanac
+            .get_seed_location_ranges()
+            .into_iter()
+            .map(|location_range| location_range.start)
+            .min();
+        assert_eq!(lowest_location, almanac.iter_all_seed_locations().min());
+        assert_eq!(lowest_location, Some(46));
+    }
+
     #[test]
     fn test_example_soil_range() {
         let almanac = get_example_almanac();
@@ -468,4 +932,97 @@ mod tests {
         assert_eq!(soil_ranges.len(), 1);
         assert_eq!(soil_ranges[0], 81..95);
     }
+
+    #[test]
+    fn test_example_map_single_range() {
+        let almanac = get_example_almanac();
+        let map = &almanac.seed_to_soil_map;
+        assert_eq!(map.map_single_range(&(79..93)), Some(81..95));
+        assert_eq!(map.map_single_range(&(10..20)), Some(10..20));
+        assert_eq!(map.map_single_range(&(100..110)), Some(100..110));
+        assert_eq!(map.map_single_range(&(96..103)), None);
+        assert_eq!(map.map_single_range(&(40..60)), None);
+    }
+
+    #[test]
+    fn test_example_seed_chunks_cover_all_seeds() {
+        let almanac = get_example_almanac();
+        let chunks = almanac.seed_chunks(5).collect::<Vec<_>>();
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 5));
+        assert_eq!(chunks.len(), 6);
+        let chunked_seeds = chunks.into_iter().flatten().collect::<Vec<_>>();
+        assert_eq!(chunked_seeds, almanac.iter_all_seeds().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_example_seed_of_min_location() {
+        let almanac = get_example_almanac();
+        assert_eq!(almanac.seed_of_min_location(), Some(13));
+        assert_eq!(almanac.seed_to_location(13), 35);
+    }
+
+    #[test]
+    fn test_example_seed_of_min_location_in_ranges() {
+        let almanac = get_example_almanac();
+        assert_eq!(almanac.seed_of_min_location_in_ranges(), Some(82));
+        assert_eq!(almanac.seed_to_location(82), 46);
+    }
+
+    #[test]
+    fn test_range_map_intersect_inside() {
+        let range_map = "52 50 48".parse::<RangeMap>().unwrap();
+        assert_eq!(range_map.intersect(&(79..93)), Some((81..95, vec![])));
+    }
+
+    #[test]
+    fn test_range_map_intersect_straddling_start() {
+        let range_map = "52 50 48".parse::<RangeMap>().unwrap();
+        let (mapped, leftover) = range_map.intersect(&(45..55)).unwrap();
+        assert_eq!(mapped, 52..57);
+        assert_eq!(leftover.len(), 1);
+        assert_eq!(leftover[0], 45..50);
+    }
+
+    #[test]
+    fn test_range_map_intersect_straddling_end() {
+        let range_map = "52 50 48".parse::<RangeMap>().unwrap();
+        let (mapped, leftover) = range_map.intersect(&(95..103)).unwrap();
+        assert_eq!(mapped, 97..100);
+        assert_eq!(leftover.len(), 1);
+        assert_eq!(leftover[0], 98..103);
+    }
+
+    #[test]
+    fn test_range_map_intersect_covering() {
+        let range_map = "50 98 2".parse::<RangeMap>().unwrap();
+        assert_eq!(
+            range_map.intersect(&(96..103)),
+            Some((50..52, vec![96..98, 100..103]))
+        );
+    }
+
+    #[test]
+    fn test_range_map_intersect_disjoint() {
+        let range_map = "50 98 2".parse::<RangeMap>().unwrap();
+        assert_eq!(range_map.intersect(&(79..93)), None);
+    }
+
+    #[test]
+    fn test_parse_almanac_extra_blank_lines_between_maps() {
+        let input = get_example_input().replace(
+            "\n\nhumidity-to-location map:",
+            "\n\n\n\nhumidity-to-location map:",
+        );
+        let almanac = Almanac::from_str(&input).unwrap();
+        assert_eq!(almanac.humidity_to_location_map.values.len(), 2);
+        assert_eq!(almanac.get_seed_locations().into_iter().min(), Some(35));
+    }
+
+    #[test]
+    fn test_parse_almanac_trailing_blank_lines() {
+        let input = get_example_input() + "\n\n\n";
+        let almanac = Almanac::from_str(&input).unwrap();
+        assert_eq!(almanac.humidity_to_location_map.values.len(), 2);
+        assert_eq!(almanac.get_seed_locations().into_iter().min(), Some(35));
+    }
 }
@@ -1,13 +1,17 @@
+use crate::parse_numbers;
+use crate::span::Span;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::ops::Range;
 use std::str::FromStr;
 use thiserror::Error;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct RaceRecords {
     records: Vec<RaceRecord>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct RaceRecord {
     time: u64,
     distance: u64,
@@ -42,27 +46,16 @@ impl FromStr for RaceRecords {
         if !times.starts_with("Time: ") {
             return Err(RaceRecordParseError::MissingTimes);
         }
-        let times = times[6..]
-            .split_whitespace()
-            .map(|time| {
-                time.parse::<u64>()
-                    .map_err(|_| RaceRecordParseError::InvalidTime(time.to_string()))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let times = parse_numbers::<u64>(&times[6..])
+            .map_err(|e| RaceRecordParseError::InvalidTime(e.0))?;
 
         let distances = lines[1];
         if !distances.starts_with("Distance: ") {
             return Err(RaceRecordParseError::MissingDistances);
         }
 
-        let distances = distances[10..]
-            .split_whitespace()
-            .map(|distance| {
-                distance
-                    .parse::<u64>()
-                    .map_err(|_| RaceRecordParseError::InvalidDistance(distance.to_string()))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let distances = parse_numbers::<u64>(&distances[10..])
+            .map_err(|e| RaceRecordParseError::InvalidDistance(e.0))?;
 
         if times.len() != distances.len() {
             return Err(RaceRecordParseError::MismatchedTimesAndDistances(
@@ -74,13 +67,45 @@ impl FromStr for RaceRecords {
         let records = times
             .into_iter()
             .zip(distances)
-            .map(|(time, distance)| RaceRecord { time, distance })
+            .map(|(time, distance)| RaceRecord::new(time, distance))
             .collect();
         Ok(RaceRecords { records })
     }
 }
 
+impl Display for RaceRecords {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let widths = self
+            .records
+            .iter()
+            .map(|record| {
+                let time = record.time.to_string().len();
+                let distance = record.distance.to_string().len();
+                time.max(distance)
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{:<9}", "Time:")?;
+        for (record, width) in self.records.iter().zip(&widths) {
+            write!(f, "  {:>width$}", record.time)?;
+        }
+        write!(f, "\n{:<9}", "Distance:")?;
+        for (record, width) in self.records.iter().zip(&widths) {
+            write!(f, "  {:>width$}", record.distance)?;
+        }
+        Ok(())
+    }
+}
+
 impl RaceRecord {
+    /// ```
+    /// use advent_of_code_2023::boat_races::RaceRecord;
+    ///
+    /// assert_eq!(RaceRecord::new(7, 9).num_ways_to_beat_record(), 4);
+    /// ```
+    pub fn new(time: u64, distance: u64) -> Self {
+        RaceRecord { time, distance }
+    }
+
     pub fn time(&self) -> u64 {
         self.time
     }
@@ -106,16 +131,12 @@ impl RaceRecord {
         let (hold1, hold2) = self.get_hold_times();
         let hold1 = self.bump_to_winner(hold1.ceil() as u64);
         let hold2 = self.bump_to_loser(hold2.floor() as u64);
-        if hold1 > hold2 {
-            hold2..hold1
-        } else {
-            hold1..hold2
-        }
+        // When the record can at best be tied the bumps cross over, leaving no winning holds.
+        hold1..hold2.max(hold1)
     }
 
     pub fn num_ways_to_beat_record(&self) -> u64 {
-        let winning_hold_times = self.get_winning_hold_times();
-        winning_hold_times.end - winning_hold_times.start
+        self.get_winning_hold_times().length()
     }
 
     fn distance_covered(&self, hold_duration: u64) -> u64 {
@@ -145,6 +166,20 @@ impl RaceRecord {
 }
 
 impl RaceRecords {
+    pub fn from_records(records: Vec<RaceRecord>) -> Self {
+        RaceRecords { records }
+    }
+
+    /// The number of ways to beat each race's record, by race index. Their product is
+    /// `num_ways_to_beat_record`.
+    pub fn solve_report(&self) -> Vec<(usize, u64)> {
+        self.records
+            .iter()
+            .map(|record| record.num_ways_to_beat_record())
+            .enumerate()
+            .collect()
+    }
+
     pub fn num_ways_to_beat_record(&self) -> u64 {
         self.records
             .iter()
@@ -152,6 +187,34 @@ impl RaceRecords {
             .product()
     }
 
+    /// The one race you get by ignoring the spaces between the numbers, as with
+    /// `patch_bad_kerning` but without a string round trip. Like parsing the patched input, this
+    /// fails if the combined time or distance does not fit in a `u64`.
+    pub fn single_race(&self) -> Result<RaceRecord, RaceRecordParseError> {
+        let time = self
+            .records
+            .iter()
+            .try_fold(0, |time, record| concat_digits(time, record.time))
+            .ok_or_else(|| RaceRecordParseError::InvalidTime(self.joined(RaceRecord::time)))?;
+        let distance = self
+            .records
+            .iter()
+            .try_fold(0, |distance, record| {
+                concat_digits(distance, record.distance)
+            })
+            .ok_or_else(|| {
+                RaceRecordParseError::InvalidDistance(self.joined(RaceRecord::distance))
+            })?;
+        Ok(RaceRecord::new(time, distance))
+    }
+
+    fn joined(&self, field: fn(&RaceRecord) -> u64) -> String {
+        self.records
+            .iter()
+            .map(|record| field(record).to_string())
+            .collect()
+    }
+
     pub fn patch_bad_kerning(s: &str) -> String {
         s.lines()
             .filter_map(|l| l.split_once(": "))
@@ -170,6 +233,16 @@ impl RaceRecords {
     }
 }
 
+fn concat_digits(high: u64, low: u64) -> Option<u64> {
+    // Nothing to shift for the first number, which may have all 20 digits a `u64` allows.
+    if high == 0 {
+        return Some(low);
+    }
+    let digits = low.checked_ilog10().unwrap_or(0) + 1;
+    high.checked_mul(10_u64.checked_pow(digits)?)?
+        .checked_add(low)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,26 +250,11 @@ mod tests {
     const EXAMPLE_INPUT: &str = "Time:      7  15   30\nDistance:  9  40  200";
 
     fn get_example_records() -> RaceRecords {
-        RaceRecords {
-            records: vec![
-                RaceRecord {
-                    time: 7,
-                    distance: 9,
-                },
-                RaceRecord {
-                    time: 15,
-                    distance: 40,
-                },
-                RaceRecord {
-                    time: 30,
-                    distance: 200,
-                },
-            ],
-        }
-    }
-
-    fn len(range: &Range<u64>) -> u64 {
-        range.end - range.start
+        RaceRecords::from_records(vec![
+            RaceRecord::new(7, 9),
+            RaceRecord::new(15, 40),
+            RaceRecord::new(30, 200),
+        ])
     }
 
     #[test]
@@ -219,7 +277,7 @@ mod tests {
         };
 
         let hold_times = example.get_winning_hold_times();
-        assert_eq!(len(&hold_times), 4);
+        assert_eq!(hold_times.length(), 4);
         assert_eq!(hold_times, 2..6)
     }
 
@@ -231,7 +289,7 @@ mod tests {
         };
 
         let hold_times = example.get_winning_hold_times();
-        assert_eq!(len(&hold_times), 8);
+        assert_eq!(hold_times.length(), 8);
         assert_eq!(hold_times, 4..12)
     }
 
@@ -243,13 +301,95 @@ mod tests {
         };
 
         let hold_times = example.get_winning_hold_times();
-        assert_eq!(len(&hold_times), 9);
+        assert_eq!(hold_times.length(), 9);
         assert_eq!(hold_times, 11..20)
     }
 
+    #[test]
+    fn test_get_winning_hold_times_perfect_square() {
+        // 10^2 - 4 * 21 = 16, so holding 3 or 7 exactly ties the record rather than beating it.
+        let record = RaceRecord::new(10, 21);
+        assert_eq!(record.get_winning_hold_times(), 4..7);
+        assert_eq!(record.num_ways_to_beat_record(), 3);
+    }
+
+    #[test]
+    fn test_get_winning_hold_times_only_ties() {
+        // 4^2 - 4 * 4 = 0: holding 2 covers exactly 4, which doesn't beat the record.
+        let record = RaceRecord::new(4, 4);
+        assert!(record.get_winning_hold_times().is_empty());
+        assert_eq!(record.num_ways_to_beat_record(), 0);
+    }
+
+    #[test]
+    fn test_get_winning_hold_times_unbeatable() {
+        let record = RaceRecord::new(4, 5);
+        assert_eq!(record.num_ways_to_beat_record(), 0);
+    }
+
     #[test]
     fn test_num_ways_to_beat_record_example() {
         let records = get_example_records();
         assert_eq!(records.num_ways_to_beat_record(), 288);
     }
+
+    #[test]
+    fn test_solve_report_example() {
+        let records = get_example_records();
+        let report = records.solve_report();
+        assert_eq!(report, vec![(0, 4), (1, 8), (2, 9)]);
+        assert_eq!(
+            report.iter().map(|(_, ways)| ways).product::<u64>(),
+            records.num_ways_to_beat_record()
+        );
+    }
+
+    #[test]
+    fn test_single_race_example() {
+        let race = get_example_records().single_race().unwrap();
+        assert_eq!(race, RaceRecord::new(71530, 940200));
+        assert_eq!(race.num_ways_to_beat_record(), 71503);
+    }
+
+    #[test]
+    fn test_single_race_overflow() {
+        let records = RaceRecords::from_records(vec![
+            RaceRecord::new(1844674407370955161, 9),
+            RaceRecord::new(5, 9),
+        ]);
+        assert_eq!(records.single_race().unwrap().time(), u64::MAX);
+
+        let records = RaceRecords::from_records(vec![
+            RaceRecord::new(1844674407370955161, 9),
+            RaceRecord::new(7, 9),
+        ]);
+        let error = records.single_race().unwrap_err();
+        assert!(
+            matches!(error, RaceRecordParseError::InvalidTime(ref time) if time == "18446744073709551617")
+        );
+        let patched = RaceRecords::patch_bad_kerning(&records.to_string());
+        assert!(matches!(
+            patched.parse::<RaceRecords>(),
+            Err(RaceRecordParseError::InvalidTime(_))
+        ));
+
+        let records = RaceRecords::from_records(vec![
+            RaceRecord::new(7, 99999999999),
+            RaceRecord::new(7, 999999999),
+        ]);
+        let error = records.single_race().unwrap_err();
+        assert!(matches!(error, RaceRecordParseError::InvalidDistance(_)));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let records = EXAMPLE_INPUT.parse::<RaceRecords>().unwrap();
+        let formatted = records.to_string();
+        assert_eq!(formatted, EXAMPLE_INPUT);
+        assert_eq!(formatted.parse::<RaceRecords>().unwrap(), records);
+        assert_eq!(
+            RaceRecords::patch_bad_kerning(&formatted),
+            RaceRecords::patch_bad_kerning(EXAMPLE_INPUT)
+        );
+    }
 }
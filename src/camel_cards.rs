@@ -1,8 +1,12 @@
-use counter::Counter;
-use std::cmp::Ordering;
-use std::fmt;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::iter::Sum;
+use core::str::FromStr;
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -13,7 +17,7 @@ pub struct Hand {
     jokers_wild: bool,
 }
 
-#[derive(Debug, PartialOrd, PartialEq, Ord, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub enum Card {
     Joker,
     Two,
@@ -31,7 +35,7 @@ pub enum Card {
     Ace,
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Ord, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
 pub enum HandType {
     HighCard,
     OnePair,
@@ -64,31 +68,14 @@ impl FromStr for Hand {
         let bid = bid
             .parse::<u64>()
             .map_err(|_| HandParseError::InvalidBid(bid.to_string()))?;
-        let cards = cards
-            .chars()
-            .map(|c| match c {
-                '2' => Ok(Card::Two),
-                '3' => Ok(Card::Three),
-                '4' => Ok(Card::Four),
-                '5' => Ok(Card::Five),
-                '6' => Ok(Card::Six),
-                '7' => Ok(Card::Seven),
-                '8' => Ok(Card::Eight),
-                '9' => Ok(Card::Nine),
-                'T' => Ok(Card::Ten),
-                'J' => Ok(Card::Jack),
-                'Q' => Ok(Card::Queen),
-                'K' => Ok(Card::King),
-                'A' => Ok(Card::Ace),
-                _ => Err(HandParseError::InvalidCard(c.to_string())),
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        Hand::new(cards, bid)
+        Hand::try_from_cards(Hand::parse_cards(cards)?, bid)
     }
 }
 
 impl Eq for Hand {}
 
+// Equality deliberately ignores the bid so that it stays consistent with `Ord`, which ranks hands
+// purely on their cards.
 impl PartialEq<Self> for Hand {
     fn eq(&self, other: &Self) -> bool {
         self.hand_type == other.hand_type && self.cards == other.cards
@@ -127,60 +114,111 @@ impl Display for Hand {
 
 impl Hand {
     pub fn new(cards: Vec<Card>, bid: u64) -> Result<Self, HandParseError> {
-        let hand_type = Hand::get_hand_type(&cards)?;
-        Ok(Hand {
-            cards,
-            hand_type,
-            bid,
-            jokers_wild: false,
-        })
+        Hand::try_from_cards(cards, bid)
     }
 
-    fn get_hand_type(cards: &[Card]) -> Result<HandType, HandParseError> {
+    /// Checks there are exactly five cards before classifying the hand.
+    pub fn try_from_cards(cards: Vec<Card>, bid: u64) -> Result<Self, HandParseError> {
         if cards.len() < 5 {
             return Err(HandParseError::InsufficientCards(cards.len()));
         }
         if cards.len() > 5 {
             return Err(HandParseError::TooManyCards(cards.len()));
         }
+        // Explicit jokers (`*`) are always wild; hands without any classify as normal.
+        let hand_type = Hand::get_hand_type_jokers_wild(&cards);
+        Ok(Hand {
+            cards,
+            hand_type,
+            bid,
+            jokers_wild: false,
+        })
+    }
+
+    /// Parses a bare card string such as `32T3K`, with a bid of 0, for ranking-only inputs.
+    pub fn from_cards_str(s: &str) -> Result<Self, HandParseError> {
+        Hand::try_from_cards(Hand::parse_cards(s)?, 0)
+    }
+
+    fn parse_cards(cards: &str) -> Result<Vec<Card>, HandParseError> {
+        cards
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                '2' => Ok(Card::Two),
+                '3' => Ok(Card::Three),
+                '4' => Ok(Card::Four),
+                '5' => Ok(Card::Five),
+                '6' => Ok(Card::Six),
+                '7' => Ok(Card::Seven),
+                '8' => Ok(Card::Eight),
+                '9' => Ok(Card::Nine),
+                'T' => Ok(Card::Ten),
+                'J' => Ok(Card::Jack),
+                'Q' => Ok(Card::Queen),
+                'K' => Ok(Card::King),
+                'A' => Ok(Card::Ace),
+                '*' => Ok(Card::Joker),
+                _ => Err(HandParseError::InvalidCard(c.to_string())),
+            })
+            .collect()
+    }
+
+    /// Like `Display`, but renders jokers as `*` so they can be told apart from jacks.
+    pub fn debug_string(&self) -> String {
+        let cards = self.cards.iter().map(Card::symbol).collect::<String>();
+        format!("{} {} ({:?})", cards, self.bid, self.hand_type)
+    }
 
-        let counts = cards.iter().collect::<Counter<_>>();
+    /// The cards from highest to lowest, e.g. `KT332` for `32T3K`, for display only.
+    pub fn display_sorted_desc(&self) -> String {
+        let mut cards = self.cards.clone();
+        cards.sort_by(|a, b| b.cmp(a));
+        cards.iter().map(|card| card.to_string()).collect()
+    }
+
+    pub fn same_cards(&self, other: &Hand) -> bool {
+        self.cards == other.cards
+    }
+
+    pub fn bid(&self) -> u64 {
+        self.bid
+    }
+
+    fn get_hand_type(cards: &[Card]) -> HandType {
+        let counts = count_cards(cards.iter());
         let max_count = counts.values().max().unwrap();
         match max_count {
-            1 => Ok(HandType::HighCard),
+            1 => HandType::HighCard,
             2 => {
                 if counts.values().filter(|&&c| c == 2).count() == 2 {
-                    Ok(HandType::TwoPair)
+                    HandType::TwoPair
                 } else {
-                    Ok(HandType::OnePair)
+                    HandType::OnePair
                 }
             }
             3 => {
                 if counts.values().filter(|&&c| c == 2).count() == 1 {
-                    Ok(HandType::FullHouse)
+                    HandType::FullHouse
                 } else {
-                    Ok(HandType::ThreeOfAKind)
+                    HandType::ThreeOfAKind
                 }
             }
-            4 => Ok(HandType::FourOfAKind),
-            5 => Ok(HandType::FiveOfAKind),
+            4 => HandType::FourOfAKind,
+            5 => HandType::FiveOfAKind,
             _ => unreachable!(),
         }
     }
 
     fn get_hand_type_jokers_wild(cards: &[Card]) -> HandType {
-        let total_count = cards.iter().collect::<Counter<_>>();
+        let total_count = count_cards(cards.iter());
         let num_jokers = total_count.get(&Card::Joker).unwrap_or(&0);
         if *num_jokers == 0 {
-            return Hand::get_hand_type(cards).unwrap();
+            return Hand::get_hand_type(cards);
         } else if *num_jokers >= 4 {
             return HandType::FiveOfAKind;
         }
 
-        let counts_no_jokers = cards
-            .iter()
-            .filter(|&card| card != &Card::Joker)
-            .collect::<Counter<_>>();
+        let counts_no_jokers = count_cards(cards.iter().filter(|&card| card != &Card::Joker));
         let max_count = counts_no_jokers.values().max().unwrap();
         let starter_type = match max_count {
             1 => HandType::HighCard,
@@ -214,47 +252,75 @@ impl Hand {
         }
     }
 
-    pub fn jokers_wild(self) -> Self {
+    pub fn jokers_wild(mut self) -> Self {
+        self.make_jokers_wild();
+        self
+    }
+
+    fn make_jokers_wild(&mut self) {
         if self.jokers_wild {
-            return self;
+            return;
         }
-        let cards = self
-            .cards
-            .into_iter()
-            .map(|card| match card {
-                Card::Jack => Card::Joker,
-                _ => card,
-            })
-            .collect::<Vec<_>>();
-        let hand_type = Self::get_hand_type_jokers_wild(&cards);
+        for card in self.cards.iter_mut() {
+            if *card == Card::Jack {
+                *card = Card::Joker;
+            }
+        }
+        self.hand_type = Self::get_hand_type_jokers_wild(&self.cards);
+        self.jokers_wild = true;
+    }
+}
 
-        Hand {
-            cards,
-            hand_type,
-            bid: self.bid,
-            jokers_wild: true,
+fn count_cards<'a>(cards: impl Iterator<Item = &'a Card>) -> BTreeMap<&'a Card, usize> {
+    let mut counts = BTreeMap::new();
+    for card in cards {
+        *counts.entry(card).or_insert(0) += 1;
+    }
+    counts
+}
+
+impl Card {
+    /// Every regular card, excluding the joker.
+    #[cfg(test)]
+    pub(crate) const ALL: [Card; 13] = [
+        Card::Two,
+        Card::Three,
+        Card::Four,
+        Card::Five,
+        Card::Six,
+        Card::Seven,
+        Card::Eight,
+        Card::Nine,
+        Card::Ten,
+        Card::Jack,
+        Card::Queen,
+        Card::King,
+        Card::Ace,
+    ];
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Card::Joker => '*',
+            Card::Two => '2',
+            Card::Three => '3',
+            Card::Four => '4',
+            Card::Five => '5',
+            Card::Six => '6',
+            Card::Seven => '7',
+            Card::Eight => '8',
+            Card::Nine => '9',
+            Card::Ten => 'T',
+            Card::Jack => 'J',
+            Card::Queen => 'Q',
+            Card::King => 'K',
+            Card::Ace => 'A',
         }
     }
 }
 
 impl Display for Card {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Card::Joker => write!(f, "J"),
-            Card::Two => write!(f, "2"),
-            Card::Three => write!(f, "3"),
-            Card::Four => write!(f, "4"),
-            Card::Five => write!(f, "5"),
-            Card::Six => write!(f, "6"),
-            Card::Seven => write!(f, "7"),
-            Card::Eight => write!(f, "8"),
-            Card::Nine => write!(f, "9"),
-            Card::Ten => write!(f, "T"),
-            Card::Jack => write!(f, "J"),
-            Card::Queen => write!(f, "Q"),
-            Card::King => write!(f, "K"),
-            Card::Ace => write!(f, "A"),
-        }
+        write!(f, "{}", self.symbol())
     }
 }
 
@@ -273,28 +339,121 @@ impl Hands {
         }
     }
 
-    pub fn get_total_winnings(&self) -> u64 {
+    /// Ranks the hands weakest first. `extend` and `collect` call this, so it is only needed
+    /// after changing the hands some other way.
+    pub fn finalize(&mut self) {
+        self.hands.sort();
+    }
+
+    pub fn len(&self) -> usize {
+        self.hands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hands.is_empty()
+    }
+
+    /// The hand at 1-based `rank`, where rank 1 is the weakest and `len()` the strongest.
+    pub fn get(&self, rank: usize) -> Option<&Hand> {
+        self.hands.get(rank.checked_sub(1)?)
+    }
+
+    /// The 1-based rank of the hand with the same cards as `hand`, if present.
+    pub fn rank_of(&self, hand: &Hand) -> Option<usize> {
         self.hands
             .iter()
-            .enumerate()
-            .map(|(i, hand)| hand.bid * (i + 1) as u64)
-            .sum()
+            .position(|h| h.same_cards(hand))
+            .map(|i| i + 1)
     }
 
-    pub fn jokers_wild(self) -> Self {
-        if self.jokers_wild {
-            return self;
+    /// How many hands there are of each type.
+    pub fn type_histogram(&self) -> BTreeMap<HandType, usize> {
+        let mut histogram = BTreeMap::new();
+        for hand in &self.hands {
+            *histogram.entry(hand.hand_type).or_insert(0) += 1;
         }
-        let mut hands = self
-            .hands
-            .into_iter()
-            .map(|hand| hand.jokers_wild())
-            .collect::<Vec<_>>();
+        histogram
+    }
+
+    /// How many hands in the set rank strictly below `hand`.
+    pub fn count_weaker_than(&self, hand: &Hand) -> usize {
+        self.hands.iter().filter(|other| *other < hand).count()
+    }
+
+    /// Parses one bare card string per line, for inputs that only need ranking.
+    pub fn from_str_no_bids(s: &str) -> Result<Self, HandParseError> {
+        let hands = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Hand::from_cards_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Hands::new(hands))
+    }
+
+    /// Assumes `hands` is sorted weakest first, which every constructor and `extend` guarantee.
+    pub fn get_total_winnings(&self) -> Winnings {
+        total_winnings(self.hands.iter())
+    }
+
+    /// Total winnings when weaker hands win, i.e. the strongest hand has rank 1.
+    pub fn get_total_winnings_reversed(&self) -> Winnings {
+        total_winnings(self.hands.iter().rev())
+    }
+
+    pub fn total_winnings_sorted(&self) -> Winnings {
+        let mut hands = self.hands.iter().collect::<Vec<_>>();
         hands.sort();
-        Self {
-            hands,
-            jokers_wild: true,
+        total_winnings(hands.into_iter())
+    }
+
+    pub fn jokers_wild(mut self) -> Self {
+        if self.jokers_wild {
+            return self;
         }
+        self.hands.iter_mut().for_each(Hand::make_jokers_wild);
+        self.hands.sort();
+        self.jokers_wild = true;
+        self
+    }
+}
+
+fn total_winnings<'a>(ranked_hands: impl Iterator<Item = &'a Hand>) -> Winnings {
+    ranked_hands
+        .enumerate()
+        .map(|(i, hand)| Winnings(hand.bid.saturating_mul((i + 1) as u64)))
+        .sum()
+}
+
+/// The total of each hand's bid multiplied by its rank. Sums saturate at `u64::MAX`, as do the
+/// products of bid and rank, like `Points` does for scratchcards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Winnings(pub u64);
+
+impl Sum for Winnings {
+    fn sum<I: Iterator<Item = Winnings>>(iter: I) -> Self {
+        iter.fold(Winnings(0), |total, winnings| {
+            Winnings(total.0.saturating_add(winnings.0))
+        })
+    }
+}
+
+impl Display for Winnings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromIterator<Hand> for Hands {
+    fn from_iter<I: IntoIterator<Item = Hand>>(iter: I) -> Self {
+        Hands::new(iter.into_iter().collect())
+    }
+}
+
+impl Extend<Hand> for Hands {
+    /// Appends every hand in `iter`, then re-ranks once.
+    fn extend<I: IntoIterator<Item = Hand>>(&mut self, iter: I) {
+        self.hands.extend(iter);
+        self.finalize();
     }
 }
 
@@ -304,6 +463,7 @@ impl FromStr for Hands {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let hands = s
             .lines()
+            .filter(|line| !line.trim().is_empty())
             .map(|line| line.parse::<Hand>())
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Hands::new(hands))
@@ -313,6 +473,14 @@ impl FromStr for Hands {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fuzz::XorShift;
+
+    fn random_hand(rng: &mut XorShift) -> Hand {
+        let cards = (0..5)
+            .map(|_| Card::ALL[rng.below(Card::ALL.len())])
+            .collect();
+        Hand::new(cards, rng.below(1000) as u64).unwrap()
+    }
 
     fn get_example_hands() -> Vec<Hand> {
         let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
@@ -353,7 +521,19 @@ mod tests {
         let hands = get_example_hands();
         let hands = Hands::new(hands);
         let total_winnings = hands.get_total_winnings();
-        assert_eq!(total_winnings, 6440);
+        assert_eq!(total_winnings, Winnings(6440));
+        assert_eq!(total_winnings.to_string(), "6440");
+    }
+
+    #[test]
+    fn test_example_hand_total_winnings_reversed() {
+        let hands = Hands::new(get_example_hands());
+        let total_winnings = hands.get_total_winnings_reversed();
+        assert_ne!(total_winnings, Winnings(6440));
+        assert_eq!(
+            total_winnings,
+            Winnings(483 + 684 * 2 + 28 * 3 + 220 * 4 + 765 * 5)
+        );
     }
 
     #[test]
@@ -362,6 +542,224 @@ mod tests {
         let hands = Hands::new(hands);
         let hands = hands.jokers_wild();
         let total_winnings = hands.get_total_winnings();
-        assert_eq!(total_winnings, 5905);
+        assert_eq!(total_winnings, Winnings(5905));
+    }
+
+    #[test]
+    fn test_hands_get_by_rank() {
+        let hands = Hands::new(get_example_hands());
+        assert_eq!(hands.len(), 5);
+        assert!(!hands.is_empty());
+        let strongest = hands.get(hands.len()).unwrap();
+        assert!(strongest.same_cards(&Hand::from_cards_str("QQQJA").unwrap()));
+        assert_eq!(strongest.bid(), 483);
+        assert_eq!(hands.get(1).unwrap().bid(), 765);
+        assert!(hands.get(0).is_none());
+        assert!(hands.get(6).is_none());
+    }
+
+    #[test]
+    fn test_hands_type_histogram() {
+        let hands = Hands::new(get_example_hands());
+        let histogram = hands.type_histogram();
+        assert_eq!(
+            histogram.into_iter().collect::<Vec<_>>(),
+            vec![
+                (HandType::OnePair, 1),
+                (HandType::TwoPair, 2),
+                (HandType::ThreeOfAKind, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hands_count_weaker_than() {
+        let hands = Hands::new(get_example_hands());
+        let strongest = "QQQJA 483".parse::<Hand>().unwrap();
+        let weakest = "32T3K 765".parse::<Hand>().unwrap();
+        assert_eq!(hands.count_weaker_than(&strongest), 4);
+        assert_eq!(hands.count_weaker_than(&weakest), 0);
+    }
+
+    #[test]
+    fn test_hands_rank_of() {
+        let hands = Hands::new(get_example_hands());
+        let hand = Hand::from_cards_str("KK677").unwrap();
+        assert_eq!(hands.rank_of(&hand), Some(3));
+        let missing = Hand::from_cards_str("AAAAA").unwrap();
+        assert_eq!(hands.rank_of(&missing), None);
+    }
+
+    #[test]
+    fn test_parse_explicit_joker() {
+        let hand = "*2345 10".parse::<Hand>().unwrap();
+        assert_eq!(hand.cards[0], Card::Joker);
+        assert_eq!(hand.hand_type, HandType::OnePair);
+        assert_eq!(hand.debug_string(), "*2345 10 (OnePair)");
+
+        let hand = "**J23 10".parse::<Hand>().unwrap();
+        assert_eq!(hand.cards[2], Card::Jack);
+        assert_eq!(hand.hand_type, HandType::ThreeOfAKind);
+    }
+
+    #[test]
+    fn test_card_display_round_trips() {
+        for card in Card::ALL.into_iter().chain([Card::Joker]) {
+            let cards = Hand::parse_cards(&card.to_string()).unwrap();
+            assert_eq!(cards, vec![card]);
+        }
+
+        let hand = "KTJJT 220".parse::<Hand>().unwrap().jokers_wild();
+        let shown = hand.to_string();
+        let (cards, _) = shown.split_once(' ').unwrap();
+        assert_eq!(Hand::from_cards_str(cards).unwrap().cards, hand.cards);
+    }
+
+    #[test]
+    fn test_parse_lowercase_cards() {
+        let hand = "t55j5 684".parse::<Hand>().unwrap();
+        let expected = "T55J5 684".parse::<Hand>().unwrap();
+        assert_eq!(hand.cards, expected.cards);
+        assert_eq!(hand.bid, expected.bid);
+        assert_eq!(hand.hand_type, expected.hand_type);
+
+        let error = "t55x5 684".parse::<Hand>().unwrap_err();
+        assert!(matches!(error, HandParseError::InvalidCard(ref c) if c == "x"));
+    }
+
+    #[test]
+    fn test_parse_hand_too_few_cards() {
+        let error = "32T3 765".parse::<Hand>().unwrap_err();
+        assert!(matches!(error, HandParseError::InsufficientCards(4)));
+    }
+
+    #[test]
+    fn test_parse_hand_too_many_cards() {
+        let error = "32T3KA 765".parse::<Hand>().unwrap_err();
+        assert!(matches!(error, HandParseError::TooManyCards(6)));
+    }
+
+    #[test]
+    fn test_parse_hands_skips_blank_lines() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        let expected = input.parse::<Hands>().unwrap();
+        for padded in [
+            "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n",
+            "32T3K 765\nT55J5 684\n\nKK677 28\nKTJJT 220\nQQQJA 483\n\n",
+        ] {
+            let hands = padded.parse::<Hands>().unwrap();
+            assert_eq!(hands.hands, expected.hands);
+            assert_eq!(hands.get_total_winnings(), expected.get_total_winnings());
+        }
+    }
+
+    #[test]
+    fn test_parse_hands_without_bids() {
+        let hands = Hands::from_str_no_bids("T55J5\n32T3K").unwrap();
+        assert_eq!(hands.hands.len(), 2);
+        assert_eq!(hands.hands[0], Hand::from_cards_str("32T3K").unwrap());
+        assert_eq!(hands.hands[0].hand_type, HandType::OnePair);
+        assert_eq!(hands.hands[1], Hand::from_cards_str("T55J5").unwrap());
+        assert_eq!(hands.hands[1].hand_type, HandType::ThreeOfAKind);
+        assert_eq!(hands.hands[1].bid(), 0);
+    }
+
+    #[test]
+    fn test_hand_equality_ignores_bid() {
+        let a = "KK677 28".parse::<Hand>().unwrap();
+        let b = "KK677 1000".parse::<Hand>().unwrap();
+        assert!(a.same_cards(&b));
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_ne!(a.bid(), b.bid());
+    }
+
+    #[test]
+    fn test_hand_equality_differing_cards() {
+        let a = "KK677 28".parse::<Hand>().unwrap();
+        let b = "KK676 28".parse::<Hand>().unwrap();
+        assert!(!a.same_cards(&b));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_hands_from_iter_and_extend() {
+        let expected = Hands::new(get_example_hands()).get_total_winnings();
+        let collected = get_example_hands().into_iter().collect::<Hands>();
+        assert_eq!(collected.get_total_winnings(), expected);
+
+        let mut hands = get_example_hands().into_iter().take(2).collect::<Hands>();
+        hands.extend(get_example_hands().into_iter().skip(2));
+        assert_eq!(hands.get_total_winnings(), expected);
+        assert_eq!(hands.get(1).unwrap().bid(), 765);
+
+        hands.extend(["AAAAA 1".parse::<Hand>().unwrap()]);
+        assert_eq!(hands.get(6).unwrap().bid(), 1);
+    }
+
+    #[test]
+    fn test_total_winnings_sorted_unsorted_input() {
+        // Skips the sort in `new` so that the unranked order is used as-is.
+        let hands = Hands {
+            hands: get_example_hands(),
+            jokers_wild: false,
+        };
+        assert_ne!(hands.get_total_winnings(), Winnings(6440));
+        assert_eq!(hands.total_winnings_sorted(), Winnings(6440));
+    }
+
+    #[test]
+    fn test_winnings_sum_saturates() {
+        let total = [Winnings(u64::MAX - 1), Winnings(2), Winnings(3)]
+            .into_iter()
+            .sum::<Winnings>();
+        assert_eq!(total, Winnings(u64::MAX));
+        assert_eq!(total.to_string(), u64::MAX.to_string());
+        assert_eq!(core::iter::empty().sum::<Winnings>(), Winnings(0));
+
+        let hands = format!("32T3K {}\nKK677 {}", u64::MAX, u64::MAX)
+            .parse::<Hands>()
+            .unwrap();
+        assert_eq!(hands.get_total_winnings(), Winnings(u64::MAX));
+    }
+
+    #[test]
+    fn test_display_sorted_desc() {
+        let hand = Hand::from_cards_str("32T3K").unwrap();
+        assert_eq!(hand.display_sorted_desc(), "KT332");
+        assert_eq!(hand.to_string(), "32T3K 0 (OnePair)");
+    }
+
+    #[test]
+    fn test_debug_string_distinguishes_jokers() {
+        let hand = "KTJJT 220".parse::<Hand>().unwrap();
+        assert_eq!(hand.debug_string(), "KTJJT 220 (TwoPair)");
+        let hand = hand.jokers_wild();
+        assert_eq!(hand.debug_string(), "KT**T 220 (FourOfAKind)");
+        assert_eq!(hand.to_string(), "KT**T 220 (FourOfAKind)");
+    }
+
+    #[test]
+    fn test_jokers_wild_in_place_is_idempotent() {
+        let hands = Hands::new(get_example_hands()).jokers_wild().jokers_wild();
+        assert!(hands
+            .hands
+            .iter()
+            .all(|hand| hand.jokers_wild && !hand.cards.contains(&Card::Jack)));
+        assert_eq!(hands.get_total_winnings(), Winnings(5905));
+    }
+
+    #[test]
+    fn test_random_hands_classify_the_same_without_jokers() {
+        let mut rng = XorShift::from_env(0x5eed_0007);
+        for _ in 0..2000 {
+            let hand = random_hand(&mut rng);
+            assert_eq!(
+                Hand::get_hand_type(&hand.cards),
+                Hand::get_hand_type_jokers_wild(&hand.cards),
+                "{}",
+                hand.debug_string()
+            );
+        }
     }
 }
@@ -0,0 +1,90 @@
+use crate::io::Source;
+use crate::{Solution, Solver};
+use std::fmt::Display;
+use thiserror::Error;
+
+/// Runs `first`, then feeds its formatted solution to `second` as input.
+#[derive(Clone, Debug)]
+pub struct ComposedSolver<A, B> {
+    first: A,
+    second: B,
+}
+
+#[derive(Debug, Error)]
+pub enum ComposedSolverError<A, B> {
+    #[error("First solver failed: {0}")]
+    First(A),
+    #[error("Second solver failed: {0}")]
+    Second(B),
+}
+
+/// Reading the original input is the first solver's job, so IO errors are attributed to it.
+impl<A: From<std::io::Error>, B> From<std::io::Error> for ComposedSolverError<A, B> {
+    fn from(error: std::io::Error) -> Self {
+        ComposedSolverError::First(A::from(error))
+    }
+}
+
+impl<A, B> ComposedSolver<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        ComposedSolver { first, second }
+    }
+}
+
+impl<T, A, B> Solver<T> for ComposedSolver<A, B>
+where
+    T: Copy + Display,
+    A: Solver<T>,
+    B: Solver<T>,
+{
+    type Err = ComposedSolverError<A::Err, B::Err>;
+
+    fn solve_str(&self, input: &str) -> Result<Solution<T>, Self::Err> {
+        let intermediate = self
+            .first
+            .solve_str(input)
+            .map_err(ComposedSolverError::First)?;
+        self.second
+            .solve(&Source::Memory(intermediate.to_string()))
+            .map_err(ComposedSolverError::Second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day0::EchoSolver;
+
+    struct FailingSolver;
+
+    impl Solver<i64> for FailingSolver {
+        type Err = std::io::Error;
+
+        fn solve_str(&self, _input: &str) -> Result<Solution<i64>, Self::Err> {
+            Err(std::io::Error::other("no solution"))
+        }
+    }
+
+    #[test]
+    fn test_compose_echo_solvers() {
+        let solver = ComposedSolver::new(EchoSolver, EchoSolver);
+        // The first solver sees 2 lines and 5 bytes, so the second is given
+        // "part 1: 2\npart 2: 5", which has 2 lines and 19 bytes.
+        let solution = solver.solve_str("ab\ncd").unwrap();
+        assert_eq!(solution, Solution::new(2, 19));
+    }
+
+    #[test]
+    fn test_compose_reports_failing_solver() {
+        let error = ComposedSolver::new(FailingSolver, EchoSolver)
+            .solve_str("")
+            .unwrap_err();
+        assert!(matches!(error, ComposedSolverError::First(_)));
+        assert_eq!(error.to_string(), "First solver failed: no solution");
+
+        let error = ComposedSolver::new(EchoSolver, FailingSolver)
+            .solve_str("")
+            .unwrap_err();
+        assert!(matches!(error, ComposedSolverError::Second(_)));
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{Solution, Solver};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Default)]
+pub struct EchoSolver;
+
+#[derive(Debug, Error)]
+pub enum EchoSolverError {
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+}
+
+impl Solver<i64> for EchoSolver {
+    type Err = EchoSolverError;
+
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
+        Ok(Solution::new(
+            input.lines().count() as i64,
+            input.len() as i64,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
+
+    #[test]
+    fn test_solve_example() -> Result<(), EchoSolverError> {
+        let input = Source::try_from("inputs/day-0-example.txt")?;
+        let result = EchoSolver.solve(&input)?;
+        assert_solution(0, &result, 3, 34);
+        Ok(())
+    }
+}
@@ -1,9 +1,8 @@
-use crate::io::Source;
 use crate::trebuchet::{sum_calibration_values, sum_calibration_values_v2, CalibrationValueError};
 use crate::{Solution, Solver};
 use thiserror::Error;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct CalibrationSolver;
 
 #[derive(Debug, Error)]
@@ -14,15 +13,13 @@ pub enum CalibrationSolverError {
     IOError(#[from] std::io::Error),
 }
 
-impl Solver<i32> for CalibrationSolver {
+impl Solver<i64> for CalibrationSolver {
     type Err = CalibrationSolverError;
 
-    fn solve(&self, input: &Source) -> Result<Solution<i32>, Self::Err> {
-        let input = input.read_string()?;
-
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
         Ok(Solution::new(
-            sum_calibration_values(&input)?,
-            sum_calibration_values_v2(&input)?,
+            i64::from(sum_calibration_values(input)?),
+            i64::from(sum_calibration_values_v2(input)?),
         ))
     }
 }
@@ -30,20 +27,23 @@ impl Solver<i32> for CalibrationSolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
 
     #[test]
-    fn test_solve_part_1() -> Result<(), CalibrationSolverError> {
+    fn test_solve() -> Result<(), CalibrationSolverError> {
         let input = Source::try_from("inputs/day-1.txt")?;
         let result = CalibrationSolver.solve(&input)?;
-        assert_eq!(result.part1(), 55029);
+        assert_solution(1, &result, 55029, 55686);
         Ok(())
     }
 
     #[test]
-    fn test_solve_part_2() -> Result<(), CalibrationSolverError> {
+    fn test_solve_reader_matches_file() -> Result<(), CalibrationSolverError> {
         let input = Source::try_from("inputs/day-1.txt")?;
-        let result = CalibrationSolver.solve(&input)?;
-        assert_eq!(result.part2(), Some(55686));
+        let mut reader = std::io::Cursor::new(input.read_string()?);
+        let result = CalibrationSolver.solve_reader(&mut reader)?;
+        assert_eq!(result, CalibrationSolver.solve(&input)?);
         Ok(())
     }
 }
@@ -1,10 +1,9 @@
-use crate::io::Source;
-use crate::snow_island::{Game, GameBag, GameParseError};
+use crate::snow_island::{GameBag, GameParseError, Games};
 use crate::{Solution, Solver};
 use log::debug;
 use thiserror::Error;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct GameSolver {
     bag: GameBag,
 }
@@ -25,62 +24,44 @@ impl Default for GameSolver {
         }
     }
 }
-impl Solver<i32> for GameSolver {
+impl Solver<i64> for GameSolver {
     type Err = GameSolverError;
-    fn solve(&self, input: &Source) -> Result<Solution<i32>, Self::Err> {
-        let games = get_games(input)?;
-        debug!("{} games loaded", games.len());
-
-        let successful_games = games
-            .iter()
-            .filter_map(|g| {
-                if self.bag.is_game_possible(g) {
-                    Some(g.id())
-                } else {
-                    None
-                }
-            })
-            .sum();
 
-        let bag_power_sum = games
-            .iter()
-            .map(|g| {
-                let bag = GameBag::minimum_for(g);
-                bag.power()
-            })
-            .sum();
-
-        Ok(Solution::new(successful_games, bag_power_sum))
+    fn dump_parsed(&self, input: &str) -> Result<Option<String>, Self::Err> {
+        Ok(Some(format!("{:#?}", input.parse::<Games>()?)))
     }
-}
 
-fn get_games(input: &Source) -> Result<Vec<Game>, GameSolverError> {
-    let input = input.read_string()?;
-    let mut games = Vec::new();
-    for line in input.lines() {
-        let game = line.parse::<Game>()?;
-        games.push(game);
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
+        let games = input.parse::<Games>()?;
+        debug!("{} games loaded", games.len());
+
+        Ok(Solution::new(
+            i64::from(games.possible_ids_sum(&self.bag)),
+            i64::from(games.minimum_bag_power_sum()),
+        ))
     }
-    Ok(games)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
 
     #[test]
-    fn test_solve_part_1() -> Result<(), GameSolverError> {
-        let input = Source::try_from("inputs/day-2.txt")?;
-        let result = GameSolver::default().solve(&input)?;
-        assert_eq!(result.part1(), 2085);
+    fn test_dump_parsed_games() -> Result<(), GameSolverError> {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let dump = GameSolver::default().dump_parsed(input)?.unwrap();
+        assert!(dump.starts_with("Games {"));
+        assert!(dump.contains("id: 1"));
         Ok(())
     }
 
     #[test]
-    fn test_solve_part_2() -> Result<(), GameSolverError> {
+    fn test_solve() -> Result<(), GameSolverError> {
         let input = Source::try_from("inputs/day-2.txt")?;
         let result = GameSolver::default().solve(&input)?;
-        assert_eq!(result.part2(), Some(79315));
+        assert_solution(2, &result, 2085, 79315);
         Ok(())
     }
 }
@@ -1,10 +1,9 @@
 use crate::gondola_lift::{EngineSchematic, EngineSchematicParseError};
-use crate::io::Source;
 use crate::{Solution, Solver};
 use log::info;
 use thiserror::Error;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct GearRatioSolver;
 
 #[derive(Debug, Error)]
@@ -15,39 +14,33 @@ pub enum GearRatioSolverError {
     ParseError(#[from] EngineSchematicParseError),
 }
 
-impl Solver<i32> for GearRatioSolver {
+impl Solver<i64> for GearRatioSolver {
     type Err = GearRatioSolverError;
-    fn solve(&self, input: &Source) -> Result<Solution<i32>, Self::Err> {
-        let input = input.read_string()?;
-        let schematic = input.parse::<EngineSchematic>()?;
 
-        let active_part_numbers = schematic.get_active_part_numbers();
-        info!("Found {} active part numbers", active_part_numbers.len());
+    fn dump_parsed(&self, input: &str) -> Result<Option<String>, Self::Err> {
+        Ok(Some(format!("{:#?}", input.parse::<EngineSchematic>()?)))
+    }
 
-        let gears = schematic.get_gears();
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
+        let schematic = input.parse::<EngineSchematic>()?;
 
-        Ok(Solution::new(
-            active_part_numbers.iter().map(|pn| pn.part_number()).sum(),
-            gears.into_iter().map(|g| g.gear_ratio()).sum(),
-        ))
+        let (part_number_sum, gear_ratio_sum) = schematic.solve_both();
+        info!("Active part numbers sum to {}", part_number_sum);
+
+        Ok(Solution::new(part_number_sum, gear_ratio_sum))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
 
     #[test]
-    fn test_solve_part_1() {
-        let input = Source::try_from("inputs/day-3.txt").unwrap();
-        let result = GearRatioSolver.solve(&input).unwrap();
-        assert_eq!(result.part1(), 556367);
-    }
-
-    #[test]
-    fn test_solve_part_2() {
+    fn test_solve() {
         let input = Source::try_from("inputs/day-3.txt").unwrap();
         let result = GearRatioSolver.solve(&input).unwrap();
-        assert_eq!(result.part2(), Some(89471771));
+        assert_solution(3, &result, 556367, 89471771);
     }
 }
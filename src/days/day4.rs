@@ -1,9 +1,8 @@
-use crate::io::Source;
 use crate::scratch_cards::{CardParseError, CardSet};
 use crate::{Solution, Solver};
 use thiserror::Error;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct ScratchCardSolver;
 
 #[derive(Debug, Error)]
@@ -14,17 +13,19 @@ pub enum ScratchCardSolverError {
     CardParseError(#[from] CardParseError),
 }
 
-impl Solver<u32> for ScratchCardSolver {
+impl Solver<i64> for ScratchCardSolver {
     type Err = ScratchCardSolverError;
 
-    fn solve(&self, input: &Source) -> Result<Solution<u32>, Self::Err> {
-        let input = input.read_string()?;
+    fn dump_parsed(&self, input: &str) -> Result<Option<String>, Self::Err> {
+        Ok(Some(format!("{:#?}", input.parse::<CardSet>()?)))
+    }
 
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
         let card_set = input.parse::<CardSet>()?;
 
         Ok(Solution::new(
-            card_set.get_points(),
-            card_set.total_instances(),
+            i64::from(card_set.get_points().0),
+            i64::from(card_set.total_instances()),
         ))
     }
 }
@@ -32,18 +33,13 @@ impl Solver<u32> for ScratchCardSolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
 
     #[test]
-    fn test_solve_part_1() {
-        let input = Source::try_from("inputs/day-4.txt").unwrap();
-        let result = ScratchCardSolver.solve(&input).unwrap();
-        assert_eq!(result.part1(), 15205);
-    }
-
-    #[test]
-    fn test_solve_part_2() {
+    fn test_solve() {
         let input = Source::try_from("inputs/day-4.txt").unwrap();
         let result = ScratchCardSolver.solve(&input).unwrap();
-        assert_eq!(result.part2(), Some(6189740));
+        assert_solution(4, &result, 15205, 6189740);
     }
 }
@@ -1,10 +1,12 @@
 use crate::almanac::{Almanac, AlmanacParseError};
-use crate::io::Source;
 use crate::{Solution, Solver};
+use log::{info, warn};
 use thiserror::Error;
 
-#[derive(Debug, Default)]
-pub struct SeedSolver;
+#[derive(Clone, Debug, Default)]
+pub struct SeedSolver {
+    max_brute_seeds: Option<usize>,
+}
 
 #[derive(Debug, Error)]
 pub enum SeedSolverError {
@@ -16,11 +18,36 @@ pub enum SeedSolverError {
     NoSeeds,
 }
 
-impl Solver<usize> for SeedSolver {
+impl SeedSolver {
+    /// Cross-checks part 2 by enumerating every seed, provided there are at most `max` of them.
+    pub fn with_max_brute_seeds(max: usize) -> Self {
+        SeedSolver {
+            max_brute_seeds: Some(max),
+        }
+    }
+
+    fn brute_force_lowest_location(&self, almanac: &Almanac) -> Option<usize> {
+        let max_brute_seeds = self.max_brute_seeds?;
+        let seed_count = almanac.seed_count();
+        if seed_count > max_brute_seeds {
+            info!(
+                "Refusing to brute force {} seeds (limit {}); only the range-based answer will be produced",
+                seed_count, max_brute_seeds
+            );
+            return None;
+        }
+        almanac.iter_all_seed_locations().min()
+    }
+}
+
+impl Solver<i64> for SeedSolver {
     type Err = SeedSolverError;
 
-    fn solve(&self, input: &Source) -> Result<Solution<usize>, Self::Err> {
-        let input = input.read_string()?;
+    fn dump_parsed(&self, input: &str) -> Result<Option<String>, Self::Err> {
+        Ok(Some(format!("{:#?}", input.parse::<Almanac>()?)))
+    }
+
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
         let almanac = input.parse::<Almanac>()?;
 
         let lowest_location_number = almanac
@@ -36,9 +63,18 @@ impl Solver<usize> for SeedSolver {
             .min()
             .ok_or(SeedSolverError::NoSeeds)?;
 
+        if let Some(lowest_location_via_seeds) = self.brute_force_lowest_location(&almanac) {
+            if lowest_location_via_seeds != lowest_location_via_ranges {
+                warn!(
+                    "Brute force found location {} but ranges found {}",
+                    lowest_location_via_seeds, lowest_location_via_ranges
+                );
+            }
+        }
+
         Ok(Solution::new(
-            lowest_location_number,
-            lowest_location_via_ranges,
+            lowest_location_number as i64,
+            lowest_location_via_ranges as i64,
         ))
     }
 }
@@ -46,18 +82,44 @@ impl Solver<usize> for SeedSolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
 
     #[test]
-    fn test_solve_part_1() {
+    fn test_solve() {
         let input = Source::try_from("inputs/day-5.txt").unwrap();
-        let result = SeedSolver.solve(&input).unwrap();
-        assert_eq!(result.part1(), 389056265);
+        let result = SeedSolver::default().solve(&input).unwrap();
+        assert_solution(5, &result, 389056265, 137516820);
+    }
+
+    fn get_example_almanac() -> Almanac {
+        let input = Source::try_from("inputs/day-5-example.txt").unwrap();
+        input.read_string().unwrap().parse().unwrap()
     }
 
     #[test]
-    fn test_solve_part_2() {
-        let input = Source::try_from("inputs/day-5.txt").unwrap();
-        let result = SeedSolver.solve(&input).unwrap();
-        assert_eq!(result.part2(), Some(137516820));
+    fn test_brute_force_refused_over_limit() {
+        let almanac = get_example_almanac();
+        assert_eq!(almanac.seed_count(), 27);
+        let solver = SeedSolver::with_max_brute_seeds(10);
+        assert_eq!(solver.brute_force_lowest_location(&almanac), None);
+    }
+
+    #[test]
+    fn test_brute_force_within_limit() {
+        let almanac = get_example_almanac();
+        let solver = SeedSolver::with_max_brute_seeds(27);
+        assert_eq!(solver.brute_force_lowest_location(&almanac), Some(46));
+        assert_eq!(
+            SeedSolver::default().brute_force_lowest_location(&almanac),
+            None
+        );
+    }
+
+    #[test]
+    fn test_solve_missing_input_reports_path() {
+        let input = Source::File("inputs/missing.txt".into());
+        let error = SeedSolver::default().solve(&input).err().unwrap();
+        assert!(error.to_string().contains("inputs/missing.txt"));
     }
 }
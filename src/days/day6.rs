@@ -1,10 +1,12 @@
 use crate::boat_races::{RaceRecordParseError, RaceRecords};
-use crate::io::Source;
 use crate::{Solution, Solver};
+use log::info;
 use thiserror::Error;
 
-#[derive(Debug, Default)]
-pub struct RaceSolver;
+#[derive(Clone, Debug, Default)]
+pub struct RaceSolver {
+    verbose: bool,
+}
 
 #[derive(Debug, Error)]
 pub enum RaceSolverError {
@@ -14,18 +16,31 @@ pub enum RaceSolverError {
     ParseError(#[from] RaceRecordParseError),
 }
 
-impl Solver<u64> for RaceSolver {
+impl RaceSolver {
+    /// Logs how many ways there are to win each race as well as solving.
+    pub fn verbose() -> Self {
+        RaceSolver { verbose: true }
+    }
+}
+
+impl Solver<i64> for RaceSolver {
     type Err = RaceSolverError;
 
-    fn solve(&self, input: &Source) -> Result<Solution<u64>, Self::Err> {
-        let input = input.read_string()?;
+    fn dump_parsed(&self, input: &str) -> Result<Option<String>, Self::Err> {
+        Ok(Some(format!("{:#?}", input.parse::<RaceRecords>()?)))
+    }
+
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
         let race_records = input.parse::<RaceRecords>()?;
+        if self.verbose {
+            for (race, ways) in race_records.solve_report() {
+                info!("race {}: {}", race, ways);
+            }
+        }
 
-        let fixed_input = RaceRecords::patch_bad_kerning(&input);
-        let fixed_race_records = fixed_input.parse::<RaceRecords>()?;
         Ok(Solution::new(
-            race_records.num_ways_to_beat_record(),
-            fixed_race_records.num_ways_to_beat_record(),
+            race_records.num_ways_to_beat_record() as i64,
+            race_records.single_race()?.num_ways_to_beat_record() as i64,
         ))
     }
 }
@@ -33,18 +48,23 @@ impl Solver<u64> for RaceSolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
 
     #[test]
-    fn test_solve_part_1() {
+    fn test_solve() {
         let input = Source::try_from("inputs/day-6.txt").unwrap();
-        let result = RaceSolver.solve(&input).unwrap();
-        assert_eq!(result.part1(), 2374848);
+        let result = RaceSolver::default().solve(&input).unwrap();
+        assert_solution(6, &result, 2374848, 39132886);
     }
 
     #[test]
-    fn test_solve_part_2() {
-        let input = Source::try_from("inputs/day-6.txt").unwrap();
-        let result = RaceSolver.solve(&input).unwrap();
-        assert_eq!(result.part2(), Some(39132886));
+    fn test_solve_single_race_overflow() {
+        let input = "Time: 4294967295 4294967295\nDistance: 1 1";
+        let error = RaceSolver::default().solve_str(input).unwrap_err();
+        assert!(matches!(
+            error,
+            RaceSolverError::ParseError(RaceRecordParseError::InvalidTime(_))
+        ));
     }
 }
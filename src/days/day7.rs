@@ -1,9 +1,9 @@
 use crate::camel_cards::{HandParseError, Hands};
-use crate::io::Source;
 use crate::{Solution, Solver};
+use log::info;
 use thiserror::Error;
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Day7Solver;
 
 #[derive(Debug, Error)]
@@ -14,37 +14,45 @@ pub enum Day7SolverError {
     ParseError(#[from] HandParseError),
 }
 
-impl Solver<u64> for Day7Solver {
+impl Solver<i64> for Day7Solver {
     type Err = Day7SolverError;
 
-    fn solve(&self, input: &Source) -> Result<Solution<u64>, Self::Err> {
-        let input = input.read_string()?;
+    fn dump_parsed(&self, input: &str) -> Result<Option<String>, Self::Err> {
+        Ok(Some(format!("{:#?}", input.parse::<Hands>()?)))
+    }
+
+    fn solve_str(&self, input: &str) -> Result<Solution<i64>, Self::Err> {
         let hands = input.parse::<Hands>()?;
+        let histogram = hands
+            .type_histogram()
+            .into_iter()
+            .rev()
+            .map(|(hand_type, count)| format!("{:?}: {}", hand_type, count))
+            .collect::<Vec<_>>();
+        info!("Hand types: {}", histogram.join(", "));
 
         let total_winnings = hands.get_total_winnings();
 
         let jokers_wild = hands.jokers_wild();
         let total_winnings_jokers_wild = jokers_wild.get_total_winnings();
 
-        Ok(Solution::new(total_winnings, total_winnings_jokers_wild))
+        Ok(Solution::new(
+            total_winnings.0 as i64,
+            total_winnings_jokers_wild.0 as i64,
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::testing::assert_solution;
+    use crate::io::Source;
 
     #[test]
-    fn test_solve_part_1() {
-        let input = Source::try_from("inputs/day-7.txt").unwrap();
-        let result = Day7Solver.solve(&input).unwrap();
-        assert_eq!(result.part1(), 248217452);
-    }
-
-    #[test]
-    fn test_solve_part_2() {
+    fn test_solve() {
         let input = Source::try_from("inputs/day-7.txt").unwrap();
         let result = Day7Solver.solve(&input).unwrap();
-        assert_eq!(result.part2(), Some(245576185));
+        assert_solution(7, &result, 248217452, 245576185);
     }
 }
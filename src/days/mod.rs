@@ -1,3 +1,7 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+pub mod day0;
 pub mod day1;
 pub mod day2;
 pub mod day3;
@@ -5,3 +9,71 @@ pub mod day4;
 pub mod day5;
 pub mod day6;
 pub mod day7;
+
+#[cfg(test)]
+pub(crate) mod testing;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DayInfo {
+    pub day: u8,
+    pub title: &'static str,
+    pub parts_solved: u8,
+}
+
+pub const DAYS: [DayInfo; 7] = [
+    DayInfo::new(1, "Trebuchet?!", 2),
+    DayInfo::new(2, "Cube Conundrum", 2),
+    DayInfo::new(3, "Gear Ratios", 2),
+    DayInfo::new(4, "Scratchcards", 2),
+    DayInfo::new(5, "If You Give A Seed A Fertilizer", 2),
+    DayInfo::new(6, "Wait For It", 2),
+    DayInfo::new(7, "Camel Cards", 2),
+];
+
+impl DayInfo {
+    const fn new(day: u8, title: &'static str, parts_solved: u8) -> Self {
+        DayInfo {
+            day,
+            title,
+            parts_solved,
+        }
+    }
+}
+
+pub struct DayListing<'a>(pub &'a [DayInfo]);
+
+impl Display for DayListing<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self.0.iter().map(|d| d.title.len()).max().unwrap_or(0);
+        for info in self.0 {
+            let parts = (1..=info.parts_solved)
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(
+                f,
+                "{:>2}  {:<width$}  (parts {})",
+                info.day, info.title, parts
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing_contains_all_days() {
+        let listing = DayListing(&DAYS).to_string();
+        let lines = listing.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 7);
+        for (i, line) in lines.iter().enumerate() {
+            assert!(line.trim_start().starts_with(&(i + 1).to_string()));
+            assert!(line.ends_with("(parts 1,2)"));
+        }
+        assert!(lines[0].contains("Trebuchet?!"));
+        assert!(lines[6].contains("Camel Cards"));
+    }
+}
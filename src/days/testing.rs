@@ -0,0 +1,56 @@
+use crate::Solution;
+use std::fmt::Display;
+
+/// Asserts both parts of a day's solution, naming the day and part on a mismatch.
+#[track_caller]
+pub fn assert_solution<T>(
+    day: u8,
+    solution: &Solution<T>,
+    expected_part1: T,
+    expected_part2: impl Into<Option<T>>,
+) where
+    T: Copy + Display + PartialEq,
+{
+    assert!(
+        solution.part1() == expected_part1,
+        "day {} part 1 mismatch: got {} expected {}",
+        day,
+        solution.part1(),
+        expected_part1
+    );
+    let expected_part2 = expected_part2.into();
+    assert!(
+        solution.part2() == expected_part2,
+        "day {} part 2 mismatch: got {} expected {}",
+        day,
+        display_part(solution.part2()),
+        display_part(expected_part2)
+    );
+}
+
+fn display_part<T: Display>(part: Option<T>) -> String {
+    part.map_or_else(|| "nothing".to_string(), |part| part.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_solution_matches() {
+        assert_solution(1, &Solution::new(1, 2), 1, 2);
+        assert_solution(1, &Solution::partial(1), 1, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "day 2 part 1 mismatch: got 3 expected 4")]
+    fn test_assert_solution_part1_mismatch() {
+        assert_solution(2, &Solution::new(3, 5), 4, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "day 2 part 2 mismatch: got nothing expected 5")]
+    fn test_assert_solution_part2_mismatch() {
+        assert_solution(2, &Solution::partial(3), 3, 5);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::almanac::Almanac;
+use crate::camel_cards::Hands;
+use crate::gondola_lift::EngineSchematic;
+use crate::scratch_cards::CardSet;
+use std::panic;
+use std::str::FromStr;
+
+const ITERATIONS: usize = 2000;
+
+/// Mostly the characters the puzzle inputs use, plus a few multibyte ones to catch parsers that
+/// slice by byte offset.
+const ALPHABET: &[char] = &[
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ' ', '\n', ':', '|', '.', '*', '#', '+', '$',
+    '-', 'T', 'J', 'Q', 'K', 'A', 's', 'e', 'd', 'o', 'i', 'l', 'm', 'a', 'p', 'C', 'r', 'G', 'é',
+    '€', '🎄',
+];
+
+const SEEDS: [&str; 4] = [
+    include_str!("../inputs/day-5-example.txt"),
+    "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..",
+    "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483",
+    "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19",
+];
+
+/// Overrides the built-in seed of every randomized test, e.g. `AOC_TEST_SEED=0x5eed0007`.
+const SEED_VAR: &str = "AOC_TEST_SEED";
+
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "xorshift never leaves a zero seed");
+        XorShift(seed)
+    }
+
+    /// Seeds from `AOC_TEST_SEED` when it is set, otherwise from `default`. The seed is printed so
+    /// that a failing test can be rerun with the same inputs.
+    pub(crate) fn from_env(default: u64) -> Self {
+        let seed = match std::env::var(SEED_VAR) {
+            Ok(value) => parse_seed(&value)
+                .unwrap_or_else(|| panic!("{} is not a valid seed: {:?}", SEED_VAR, value)),
+            Err(_) => default,
+        };
+        eprintln!("{}={:#x}", SEED_VAR, seed);
+        XorShift::new(seed)
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub(crate) fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn char(&mut self) -> char {
+        ALPHABET[self.below(ALPHABET.len())]
+    }
+
+    fn random_string(&mut self) -> String {
+        let len = self.below(64);
+        (0..len).map(|_| self.char()).collect()
+    }
+
+    fn mutate(&mut self, seed: &str) -> String {
+        let mut chars = seed.chars().collect::<Vec<_>>();
+        for _ in 0..=self.below(8) {
+            let pos = self.below(chars.len() + 1);
+            match self.below(3) {
+                0 if pos < chars.len() => chars[pos] = self.char(),
+                1 if pos < chars.len() => {
+                    chars.remove(pos);
+                }
+                _ => {
+                    let repeat = self.below(24) + 1;
+                    let c = self.char();
+                    chars.splice(pos..pos, std::iter::repeat_n(c, repeat));
+                }
+            }
+        }
+        chars.into_iter().collect()
+    }
+
+    fn inputs(&mut self) -> Vec<String> {
+        (0..ITERATIONS)
+            .map(|i| {
+                if i % 2 == 0 {
+                    self.random_string()
+                } else {
+                    let seed = SEEDS[self.below(SEEDS.len())];
+                    self.mutate(seed)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Accepts a non-zero seed in decimal or `0x`-prefixed hex.
+fn parse_seed(value: &str) -> Option<u64> {
+    let value = value.trim();
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+    .filter(|seed| *seed != 0)
+}
+
+fn assert_never_panics<T: FromStr>(name: &str, seed: u64) {
+    let mut rng = XorShift::from_env(seed);
+    for input in rng.inputs() {
+        let result = panic::catch_unwind(|| {
+            let _ = input.parse::<T>();
+        });
+        assert!(result.is_ok(), "{} parser panicked on {:?}", name, input);
+    }
+}
+
+#[test]
+fn test_almanac_parse_never_panics() {
+    assert_never_panics::<Almanac>("Almanac", 0x5eed_0005);
+}
+
+#[test]
+fn test_engine_schematic_parse_never_panics() {
+    assert_never_panics::<EngineSchematic>("EngineSchematic", 0x5eed_0003);
+}
+
+#[test]
+fn test_hands_parse_never_panics() {
+    assert_never_panics::<Hands>("Hands", 0x5eed_0007);
+}
+
+#[test]
+fn test_card_set_parse_never_panics() {
+    assert_never_panics::<CardSet>("CardSet", 0x5eed_0004);
+}
+
+#[test]
+fn test_same_seed_same_sequence() {
+    let (mut a, mut b) = (XorShift::new(0x5eed), XorShift::new(0x5eed));
+    assert_eq!(a.inputs(), b.inputs());
+    assert_ne!(XorShift::new(0x5eed).next(), XorShift::new(0x5eee).next());
+}
+
+#[test]
+fn test_parse_seed() {
+    assert_eq!(parse_seed("1234"), Some(1234));
+    assert_eq!(parse_seed(" 0x5eed0007\n"), Some(0x5eed_0007));
+    assert_eq!(parse_seed("0"), None);
+    assert_eq!(parse_seed("seed"), None);
+}
@@ -1,7 +1,12 @@
+use crate::span;
 use crate::span::Span;
-use std::fmt::{Display, Formatter};
-use std::ops::Range;
-use std::str::FromStr;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::min;
+use core::fmt::{Display, Formatter};
+use core::ops::Range;
+use core::str::FromStr;
 use thiserror::Error;
 
 #[derive(Debug, PartialEq)]
@@ -38,12 +43,20 @@ pub struct PositionedComponent {
 pub enum EngineSchematicParseError {
     #[error("This should not have happened")]
     None,
+    #[error("Invalid input: invalid part number: {0}")]
+    InvalidPartNumber(String),
+    #[error("Invalid input: non-ASCII byte at offset {0}")]
+    NonAsciiByte(usize),
 }
 
 impl FromStr for EngineSchematic {
     type Err = EngineSchematicParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Spans are byte offsets that double as columns, which only holds for ASCII.
+        if let Some(offset) = s.bytes().position(|b| !b.is_ascii()) {
+            return Err(EngineSchematicParseError::NonAsciiByte(offset));
+        }
         let mut lines = Vec::new();
         for line in s.lines() {
             lines.push(line.parse()?)
@@ -52,7 +65,24 @@ impl FromStr for EngineSchematic {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Parses a grid of ASCII bytes, such as a memory-mapped input, reporting the offset of the
+/// first byte that is not ASCII. Valid UTF-8 is left to `from_str` to reject.
+impl TryFrom<&[u8]> for EngineSchematic {
+    type Error = EngineSchematicParseError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        core::str::from_utf8(bytes)
+            .map_err(|e| {
+                // The valid prefix may already hold a non-ASCII char ahead of the bad bytes.
+                let valid = &bytes[..e.valid_up_to()];
+                let offset = valid.iter().position(|b| !b.is_ascii());
+                EngineSchematicParseError::NonAsciiByte(offset.unwrap_or(valid.len()))
+            })?
+            .parse()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct ActivePartNumber {
     part_number: i32,
     line: usize,
@@ -79,7 +109,41 @@ impl Gear {
     }
 }
 
+/// A symbol and the part numbers adjacent to it.
+#[derive(Debug, PartialEq)]
+pub struct SymbolAdjacency {
+    symbol: char,
+    line: usize,
+    col: usize,
+    part_numbers: Vec<ActivePartNumber>,
+}
+
+impl SymbolAdjacency {
+    pub fn symbol(&self) -> char {
+        self.symbol
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn col(&self) -> usize {
+        self.col
+    }
+
+    pub fn part_numbers(&self) -> &[ActivePartNumber] {
+        &self.part_numbers
+    }
+}
+
 impl EngineSchematic {
+    /// Stacks `other` below this schematic, so symbols on either side of the join activate
+    /// numbers on the other.
+    pub fn concat(mut self, other: Self) -> Self {
+        self.lines.extend(other.lines);
+        self
+    }
+
     fn get_symbols(&self) -> Vec<PositionedComponent> {
         let mut symbols = Vec::new();
         for (line, schematic_line) in self.lines.iter().enumerate() {
@@ -97,37 +161,83 @@ impl EngineSchematic {
         symbols
     }
 
-    fn get_symbols_matching(&self, symbol: char) -> Vec<PositionedComponent> {
+    /// Every symbol in the schematic with the part numbers it touches, top-left first.
+    pub fn symbol_report(&self) -> Vec<SymbolAdjacency> {
         self.get_symbols()
             .into_iter()
-            .filter(|c| c.component == Component::Symbol(symbol))
+            .filter_map(|component| {
+                let Component::Symbol(symbol) = component.component else {
+                    return None;
+                };
+                let mut part_numbers = self.get_adjacent_part_numbers(&component);
+                part_numbers.sort_by_key(|p| (p.line, p.span.start));
+                Some(SymbolAdjacency {
+                    symbol,
+                    line: component.line,
+                    col: component.span.start,
+                    part_numbers,
+                })
+            })
             .collect()
     }
 
     pub fn get_gears(&self) -> Vec<Gear> {
-        self.get_symbols_matching('*')
+        self.symbol_report()
             .into_iter()
-            .filter_map(|g| self.get_gear(g))
+            .filter(|adjacency| adjacency.symbol == '*' && adjacency.part_numbers.len() == 2)
+            .map(|mut adjacency| {
+                let second_gear = adjacency.part_numbers.pop().unwrap();
+                let first_gear = adjacency.part_numbers.pop().unwrap();
+                Gear {
+                    line: adjacency.line,
+                    span: adjacency.col..adjacency.col + 1,
+                    first_gear,
+                    second_gear,
+                }
+            })
             .collect()
     }
 
-    fn get_gear(&self, component: PositionedComponent) -> Option<Gear> {
-        match component.component {
-            Component::Symbol('*') => {
-                let mut part_numbers = self.get_adjacent_part_numbers(&component);
-                if part_numbers.len() == 2 {
-                    Some(Gear {
-                        line: component.line,
-                        span: component.span,
-                        first_gear: part_numbers.pop().unwrap(),
-                        second_gear: part_numbers.pop().unwrap(),
-                    })
-                } else {
-                    None
-                }
+    /// Computes the sum of active part numbers and the sum of gear ratios in a single pass over the
+    /// symbols, rather than scanning the grid once for each.
+    pub fn solve_both(&self) -> (i64, i64) {
+        let mut active_part_numbers = BTreeMap::new();
+        let mut gear_ratio_sum = 0;
+        for symbol in self.get_symbols() {
+            let part_numbers = self.get_adjacent_part_numbers(&symbol);
+            if symbol.component == Component::Symbol('*') && part_numbers.len() == 2 {
+                gear_ratio_sum += part_numbers
+                    .iter()
+                    .map(|p| p.part_number as i64)
+                    .product::<i64>();
+            }
+            for part_number in part_numbers {
+                let key = (part_number.line, part_number.span.start);
+                active_part_numbers.insert(key, part_number.part_number as i64);
             }
-            _ => None,
         }
+        (active_part_numbers.values().sum(), gear_ratio_sum)
+    }
+
+    /// Part numbers that participate in more than one gear, in schematic order.
+    pub fn part_numbers_in_multiple_gears(&self) -> Vec<ActivePartNumber> {
+        let mut gear_counts: BTreeMap<(usize, usize, usize), (ActivePartNumber, usize)> =
+            BTreeMap::new();
+        for gear in self.get_gears() {
+            for part_number in [gear.first_gear, gear.second_gear] {
+                let key = (
+                    part_number.line,
+                    part_number.span.start,
+                    part_number.span.end,
+                );
+                gear_counts.entry(key).or_insert((part_number, 0)).1 += 1;
+            }
+        }
+        gear_counts
+            .into_values()
+            .filter(|(_, count)| *count > 1)
+            .map(|(part_number, _)| part_number)
+            .collect()
     }
 
     fn get_adjacent_part_numbers(&self, component: &PositionedComponent) -> Vec<ActivePartNumber> {
@@ -210,6 +320,33 @@ impl EngineSchematic {
 
         active_part_numbers
     }
+
+    /// Like `get_active_part_numbers`, but a symbol activates any number within `radius` cells,
+    /// so `radius = 1` gives the standard 8-neighbour adjacency.
+    pub fn get_active_part_numbers_within(&self, radius: usize) -> Vec<ActivePartNumber> {
+        let mut active_part_numbers = Vec::new();
+        for (line, schematic_line) in self.lines.iter().enumerate() {
+            for component in &schematic_line.components {
+                if let Component::PartNumber(part_number) = component.component {
+                    let span = component.intersect_span_within(radius);
+                    let first_line = line.saturating_sub(radius);
+                    let last_line = min(line + radius, self.lines.len() - 1);
+                    let is_active = self.lines[first_line..=last_line]
+                        .iter()
+                        .any(|l| l.has_symbol_overlapping_range(&span));
+                    if is_active {
+                        active_part_numbers.push(ActivePartNumber {
+                            part_number,
+                            line,
+                            span: component.span.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        active_part_numbers
+    }
 }
 
 impl FromStr for SchematicLine {
@@ -218,7 +355,7 @@ impl FromStr for SchematicLine {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut components = Vec::new();
         let mut offset = 0;
-        while let Some((component, new_offset)) = SchematicComponent::parse_component(s, offset) {
+        while let Some((component, new_offset)) = SchematicComponent::parse_component(s, offset)? {
             components.push(component);
             offset = new_offset;
         }
@@ -227,7 +364,7 @@ impl FromStr for SchematicLine {
 }
 
 impl Display for SchematicLine {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         for component in &self.components {
             write!(f, "{}", component)?;
         }
@@ -237,13 +374,14 @@ impl Display for SchematicLine {
 
 impl SchematicLine {
     pub fn has_symbol_overlapping_range(&self, range: &Range<usize>) -> bool {
-        self.components.iter().any(|c| {
-            if let Component::Symbol(_) = c.component {
-                range.overlaps(&c.span)
-            } else {
-                false
-            }
-        })
+        self.overlapping_components(range)
+            .into_iter()
+            .any(|c| matches!(c.component, Component::Symbol(_)))
+    }
+
+    /// Components are parsed left to right, so they are sorted and never overlap each other.
+    fn overlapping_components(&self, range: &Range<usize>) -> Vec<&SchematicComponent> {
+        span::overlapping_sorted_by(&self.components, range, |c| &c.span)
     }
 
     fn get_adjacent_components(&self, range: &Range<usize>) -> Vec<&SchematicComponent> {
@@ -257,10 +395,8 @@ impl SchematicLine {
         &self,
         range: &Range<usize>,
     ) -> Vec<&SchematicComponent> {
-        self.components
-            .iter()
-            .filter(|c| c.span.overlaps_or_is_adjacent_to(range))
-            .collect()
+        // A component touches `range` exactly when it overlaps `range` widened by one each side.
+        self.overlapping_components(&(range.start.saturating_sub(1)..range.end + 1))
     }
 }
 
@@ -277,30 +413,44 @@ impl SchematicComponent {
         }
     }
 
-    fn parse_component(s: &str, offset: usize) -> Option<(SchematicComponent, usize)> {
+    fn intersect_span_within(&self, radius: usize) -> Range<usize> {
+        self.span.start.saturating_sub(radius)..self.span.end + radius
+    }
+
+    fn parse_component(
+        s: &str,
+        offset: usize,
+    ) -> Result<Option<(SchematicComponent, usize)>, EngineSchematicParseError> {
         let sub = &s[offset..];
         if sub.is_empty() {
-            return None;
+            return Ok(None);
         }
 
-        Self::parse_part_number(sub, offset)
-            .or_else(|| Self::parse_space(sub, offset))
-            .or_else(|| Self::parse_symbol(sub, offset))
+        if let Some(part_number) = Self::parse_part_number(sub, offset)? {
+            return Ok(Some(part_number));
+        }
+
+        Ok(Self::parse_space(sub, offset).or_else(|| Self::parse_symbol(sub, offset)))
     }
 
-    fn parse_part_number(s: &str, offset: usize) -> Option<(SchematicComponent, usize)> {
+    fn parse_part_number(
+        s: &str,
+        offset: usize,
+    ) -> Result<Option<(SchematicComponent, usize)>, EngineSchematicParseError> {
         let size = s.chars().take_while(|c| c.is_ascii_digit()).count();
         if size > 0 {
-            let part_number = s[..size].parse::<i32>().unwrap();
-            Some((
+            let part_number = s[..size]
+                .parse::<i32>()
+                .map_err(|_| EngineSchematicParseError::InvalidPartNumber(s[..size].to_string()))?;
+            Ok(Some((
                 SchematicComponent {
                     span: offset..offset + size,
                     component: Component::PartNumber(part_number),
                 },
                 offset + size,
-            ))
+            )))
         } else {
-            None
+            Ok(None)
         }
     }
 
@@ -332,7 +482,7 @@ impl SchematicComponent {
 }
 
 impl Display for SchematicComponent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match &self.component {
             Component::PartNumber(part_number) => write!(f, "{}", part_number)?,
             Component::Symbol(symbol) => write!(f, "{}", symbol)?,
@@ -399,6 +549,73 @@ mod tests {
         assert_eq!(sum_part_numbers, 4361);
     }
 
+    #[test]
+    fn test_parse_from_bytes() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        let schematic = EngineSchematic::try_from(input.as_bytes()).unwrap();
+        assert_eq!(schematic, input.parse::<EngineSchematic>().unwrap());
+    }
+
+    #[test]
+    fn test_parse_non_ascii() {
+        let result = "1é2*".parse::<EngineSchematic>();
+        assert_eq!(result, Err(EngineSchematicParseError::NonAsciiByte(1)));
+        let result = "..\n.€".parse::<EngineSchematic>();
+        assert_eq!(result, Err(EngineSchematicParseError::NonAsciiByte(4)));
+    }
+
+    #[test]
+    fn test_parse_from_non_ascii_bytes() {
+        let result = EngineSchematic::try_from("12.\n.é*".as_bytes());
+        assert_eq!(result, Err(EngineSchematicParseError::NonAsciiByte(5)));
+
+        let result = EngineSchematic::try_from(&b"12.\n.\xff*"[..]);
+        assert_eq!(result, Err(EngineSchematicParseError::NonAsciiByte(5)));
+        // "1é.\n" followed by a byte that is never valid UTF-8.
+        let result = EngineSchematic::try_from(&b"1\xc3\xa9.\n\xff"[..]);
+        assert_eq!(result, Err(EngineSchematicParseError::NonAsciiByte(1)));
+    }
+
+    #[test]
+    fn test_concat_activates_across_join() {
+        let top = "467..114..\n...*......\n..35..633.";
+        let bottom =
+            "......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        let sum = |schematic: &EngineSchematic| {
+            schematic
+                .get_active_part_numbers()
+                .iter()
+                .map(|p| p.part_number)
+                .sum::<i32>()
+        };
+        let top = top.parse::<EngineSchematic>().unwrap();
+        let bottom = bottom.parse::<EngineSchematic>().unwrap();
+        assert_eq!(sum(&top) + sum(&bottom), 4361 - 633);
+        assert_eq!(sum(&top.concat(bottom)), 4361);
+    }
+
+    #[test]
+    fn test_active_part_numbers_within_radius_one_matches_standard() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        let schematic = input.parse::<EngineSchematic>().unwrap();
+        let active_part_numbers = schematic.get_active_part_numbers_within(1);
+        assert_eq!(active_part_numbers, schematic.get_active_part_numbers());
+        let sum_part_numbers = active_part_numbers
+            .iter()
+            .map(|p| p.part_number)
+            .sum::<i32>();
+        assert_eq!(sum_part_numbers, 4361);
+    }
+
+    #[test]
+    fn test_active_part_numbers_within_radius_two() {
+        let schematic = "12...\n.....\n...#.".parse::<EngineSchematic>().unwrap();
+        assert!(schematic.get_active_part_numbers_within(1).is_empty());
+        let active_part_numbers = schematic.get_active_part_numbers_within(2);
+        assert_eq!(active_part_numbers.len(), 1);
+        assert_eq!(active_part_numbers[0].part_number(), 12);
+    }
+
     #[test]
     fn test_find_engine_schematic_gears() {
         let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
@@ -410,4 +627,78 @@ mod tests {
         assert_eq!(gears[1].line, 8);
         assert_eq!(gears[1].gear_ratio(), 451490);
     }
+
+    #[test]
+    fn test_symbol_report() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        let schematic = input.parse::<EngineSchematic>().unwrap();
+        let report = schematic.symbol_report();
+        assert_eq!(report.len(), 6);
+        let star = &report[0];
+        assert_eq!((star.symbol(), star.line(), star.col()), ('*', 1, 3));
+        assert_eq!(
+            star.part_numbers()
+                .iter()
+                .map(ActivePartNumber::part_number)
+                .collect::<Vec<_>>(),
+            vec![467, 35]
+        );
+        let single = report.iter().find(|a| a.line() == 4).unwrap();
+        assert_eq!(single.symbol(), '*');
+        assert_eq!(single.part_numbers().len(), 1);
+    }
+
+    #[test]
+    fn test_gear_part_numbers_in_schematic_order() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        let schematic = input.parse::<EngineSchematic>().unwrap();
+        let gears = schematic.get_gears();
+        assert_eq!(gears[0].first_gear.part_number(), 467);
+        assert_eq!(gears[0].second_gear.part_number(), 35);
+        assert_eq!(gears[1].first_gear.part_number(), 755);
+        assert_eq!(gears[1].second_gear.part_number(), 598);
+    }
+
+    #[test]
+    fn test_part_numbers_in_multiple_gears() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        let schematic = input.parse::<EngineSchematic>().unwrap();
+        assert!(schematic.part_numbers_in_multiple_gears().is_empty());
+
+        let schematic = "12*34*56".parse::<EngineSchematic>().unwrap();
+        let shared = schematic.part_numbers_in_multiple_gears();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].part_number(), 34);
+        assert_eq!(shared[0].line, 0);
+        assert_eq!(shared[0].span, 3..5);
+    }
+
+    #[test]
+    fn test_solve_both_matches_separate_passes() {
+        let input = "467..114..\n...*......\n..35..633.\n......#...\n617*......\n.....+.58.\n..592.....\n......755.\n...$.*....\n.664.598..";
+        let schematic = input.parse::<EngineSchematic>().unwrap();
+        let part_number_sum = schematic
+            .get_active_part_numbers()
+            .iter()
+            .map(|p| p.part_number() as i64)
+            .sum::<i64>();
+        let gear_ratio_sum = schematic
+            .get_gears()
+            .iter()
+            .map(|g| g.gear_ratio() as i64)
+            .sum::<i64>();
+        assert_eq!(schematic.solve_both(), (part_number_sum, gear_ratio_sum));
+        assert_eq!(schematic.solve_both(), (4361, 467835));
+    }
+
+    #[test]
+    fn test_parse_oversized_part_number() {
+        let result = "..44444444444444444.#".parse::<EngineSchematic>();
+        assert_eq!(
+            result,
+            Err(EngineSchematicParseError::InvalidPartNumber(
+                "44444444444444444".to_string()
+            ))
+        );
+    }
 }
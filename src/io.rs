@@ -1,31 +1,112 @@
 use log::trace;
 use std::fmt::{Display, Formatter};
+use std::io::Read;
 use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
 use std::{fmt, io};
 
+/// Looks up an environment variable, like `std::env::var`.
+type EnvLookup = dyn Fn(&str) -> Result<String, std::env::VarError>;
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum Source {
     #[default]
     Stdin,
     File(PathBuf),
+    /// The value of the named environment variable.
+    Env(String),
+    /// Input that has already been read.
+    Memory(String),
 }
 
 impl Source {
     pub fn open(&self) -> io::Result<Box<dyn io::BufRead>> {
+        self.open_with_env(&|name| std::env::var(name))
+    }
+
+    /// Like `open`, but looks up `Env` sources with `env` rather than in the process environment.
+    fn open_with_env(&self, env: &EnvLookup) -> io::Result<Box<dyn io::BufRead>> {
         match self {
             Source::Stdin => Ok(Box::new(io::stdin().lock())),
             Source::File(path) => Ok(Box::new(io::BufReader::new(std::fs::File::open(path)?))),
+            Source::Env(name) => {
+                let value = env(name).map_err(|e| io::Error::new(io::ErrorKind::NotFound, e))?;
+                Ok(Box::new(io::Cursor::new(value.into_bytes())))
+            }
+            Source::Memory(value) => Ok(Box::new(io::Cursor::new(value.clone().into_bytes()))),
         }
     }
 
     pub fn read_string(&self) -> io::Result<String> {
+        self.read_string_with_env(&|name| std::env::var(name))
+    }
+
+    fn read_string_with_env(&self, env: &EnvLookup) -> io::Result<String> {
         trace!("Reading from {}", self);
-        let mut reader = self.open()?;
         let mut buffer = String::new();
-        reader.read_to_string(&mut buffer)?;
+        self.open_with_env(env)
+            .and_then(|mut reader| reader.read_to_string(&mut buffer))
+            .map_err(|e| with_path_context(e, "failed to read", self))?;
         trace!("Read {} bytes", buffer.len());
         Ok(buffer)
     }
+
+    /// Like `read_string`, but gives up on stdin if it hasn't reached the end after `timeout`.
+    /// Other sources are read as normal.
+    pub fn read_string_timeout(&self, timeout: Duration) -> io::Result<String> {
+        match self {
+            Source::Stdin => read_to_string_timeout(io::stdin(), timeout)
+                .map_err(|e| with_path_context(e, "failed to read", self)),
+            _ => self.read_string(),
+        }
+    }
+
+    /// Size and modification time of a file source, or `None` for the other sources.
+    pub fn metadata(&self) -> io::Result<Option<SourceMeta>> {
+        match self {
+            Source::Stdin | Source::Env(_) | Source::Memory(_) => Ok(None),
+            Source::File(path) => {
+                let metadata = std::fs::metadata(path)
+                    .map_err(|e| with_path_context(e, "failed to stat", self))?;
+                Ok(Some(SourceMeta {
+                    len: metadata.len(),
+                    modified: metadata.modified().ok(),
+                }))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceMeta {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Reads on a background thread, as stdin can't be polled portably. On timeout the thread is left
+/// blocked on the reader.
+fn read_to_string_timeout<R: Read + Send + 'static>(
+    mut reader: R,
+    timeout: Duration,
+) -> io::Result<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut buffer = String::new();
+        let result = reader.read_to_string(&mut buffer).map(|_| buffer);
+        let _ = sender.send(result);
+    });
+    receiver.recv_timeout(timeout).unwrap_or_else(|_| {
+        Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("no end of input after {:?}", timeout),
+        ))
+    })
+}
+
+fn with_path_context(error: io::Error, action: &str, path: impl Display) -> io::Error {
+    io::Error::new(error.kind(), format!("{action} {path}: {error}"))
 }
 
 impl Display for Source {
@@ -33,6 +114,8 @@ impl Display for Source {
         match self {
             Source::Stdin => write!(f, "<stdin>"),
             Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(name) => write!(f, "<env:{}>", name),
+            Source::Memory(_) => write!(f, "<memory>"),
         }
     }
 }
@@ -43,8 +126,14 @@ impl TryFrom<&str> for Source {
     fn try_from(s: &str) -> Result<Self, io::Error> {
         if s == "-" {
             Ok(Source::Stdin)
+        } else if let Some(name) = s.strip_prefix("env:") {
+            Ok(Source::Env(name.to_string()))
         } else {
-            Ok(Source::File(PathBuf::from(s).canonicalize()?))
+            let path = PathBuf::from(s);
+            let path = path
+                .canonicalize()
+                .map_err(|e| with_path_context(e, "failed to resolve", path.display()))?;
+            Ok(Source::File(path))
         }
     }
 }
@@ -73,4 +162,85 @@ mod tests {
             Source::File(PathBuf::from("Cargo.toml").canonicalize().unwrap())
         );
     }
+
+    #[test]
+    fn test_source_from_missing_path_includes_path() {
+        let error = Source::try_from("inputs/missing.txt").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("inputs/missing.txt"));
+    }
+
+    #[test]
+    fn test_read_string_missing_file_includes_path() {
+        let source = Source::File(PathBuf::from("inputs/missing.txt"));
+        let error = source.read_string().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error
+            .to_string()
+            .starts_with("failed to read inputs/missing.txt: "));
+    }
+
+    #[test]
+    fn test_source_metadata() {
+        let source = Source::try_from("Cargo.toml").unwrap();
+        let meta = source.metadata().unwrap().unwrap();
+        assert!(meta.len > 0);
+        assert_eq!(Source::Stdin.metadata().unwrap(), None);
+    }
+
+    #[test]
+    fn test_source_from_env() {
+        let source = Source::try_from("env:AOC_TEST_SOURCE_INPUT").unwrap();
+        assert_eq!(source, Source::Env("AOC_TEST_SOURCE_INPUT".to_string()));
+        assert_eq!(source.to_string(), "<env:AOC_TEST_SOURCE_INPUT>");
+        // Setting a real variable would race with other tests reading the environment.
+        let env = |name: &str| match name {
+            "AOC_TEST_SOURCE_INPUT" => Ok("1abc2\npqr3stu8vwx".to_string()),
+            _ => Err(std::env::VarError::NotPresent),
+        };
+        assert_eq!(
+            source.read_string_with_env(&env).unwrap(),
+            "1abc2\npqr3stu8vwx"
+        );
+    }
+
+    #[test]
+    fn test_source_from_unset_env() {
+        let source = Source::Env("AOC_TEST_SOURCE_UNSET".to_string());
+        let error = source.read_string().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(error.to_string().contains("<env:AOC_TEST_SOURCE_UNSET>"));
+    }
+
+    struct NeverEof;
+
+    impl Read for NeverEof {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            thread::sleep(Duration::from_secs(3600));
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn test_read_to_string_timeout_fires() {
+        let error = read_to_string_timeout(NeverEof, Duration::from_millis(10)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(error.to_string().contains("no end of input"));
+    }
+
+    #[test]
+    fn test_read_to_string_timeout_reads() {
+        let reader = io::Cursor::new("1abc2".as_bytes().to_vec());
+        let input = read_to_string_timeout(reader, Duration::from_secs(60)).unwrap();
+        assert_eq!(input, "1abc2");
+    }
+
+    #[test]
+    fn test_source_memory() {
+        let source = Source::Memory("1abc2".to_string());
+        assert_eq!(source.read_string().unwrap(), "1abc2");
+        assert_eq!(source.read_string_timeout(Duration::ZERO).unwrap(), "1abc2");
+        assert_eq!(source.to_string(), "<memory>");
+        assert_eq!(source.metadata().unwrap(), None);
+    }
 }
@@ -1,10 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 extern crate core;
 
-use std::fmt;
-use std::fmt::{Display, Formatter};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+use thiserror::Error;
 
+#[cfg(feature = "std")]
 pub mod io;
 
+#[cfg(feature = "std")]
 pub mod days;
 pub mod trebuchet;
 
@@ -16,12 +25,30 @@ pub mod scratch_cards;
 
 pub mod almanac;
 
+// Solving the race quadratic needs `f64::sqrt`, which core does not provide.
+#[cfg(feature = "std")]
 pub mod boat_races;
 
 pub mod camel_cards;
 
 pub mod span;
 
+#[cfg(test)]
+mod fuzz;
+
+#[cfg(test)]
+mod test_log;
+
+#[cfg(feature = "std")]
+pub mod timing;
+
+#[cfg(feature = "std")]
+pub mod compose;
+
+#[cfg(feature = "dhat")]
+pub mod profiling;
+
+#[derive(Debug, PartialEq)]
 pub struct Solution<T: Display> {
     part1: T,
     part2: Option<T>,
@@ -39,27 +66,121 @@ impl<T: Copy + Display> Solution<T> {
         Solution { part1, part2: None }
     }
 
+    pub fn from_parts(part1: T, part2: Option<T>) -> Self {
+        Solution { part1, part2 }
+    }
+
     pub fn part1(&self) -> T {
         self.part1
     }
     pub fn part2(&self) -> Option<T> {
         self.part2
     }
+
+    pub fn is_complete(&self) -> bool {
+        self.part2.is_some()
+    }
+
+    pub fn parts(&self) -> impl Iterator<Item = (u8, &T)> {
+        core::iter::once((1, &self.part1)).chain(self.part2.as_ref().map(|part2| (2, part2)))
+    }
+
+    /// Formats the solution on a single line, e.g. `part 1: 142 | part 2: 281`.
+    pub fn format_inline(&self) -> String {
+        let mut inline = String::new();
+        self.write_parts(&mut inline, " | ")
+            .expect("writing to a String cannot fail");
+        inline
+    }
+
+    fn write_parts(&self, out: &mut impl fmt::Write, separator: &str) -> fmt::Result {
+        for (part, value) in self.parts() {
+            if part > 1 {
+                out.write_str(separator)?;
+            }
+            write!(out, "part {}: {}", part, value)?;
+        }
+        Ok(())
+    }
 }
 
 impl<T: Copy + Display> Display for Solution<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "part 1: {}", self.part1)?;
-        if let Some(part2) = self.part2 {
-            write!(f, "\npart 2: {}", part2)?;
+        self.write_parts(f, "\n")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Copy + Display> Solution<T> {
+    pub fn pretty(&self, color: bool) -> PrettySolution<'_, T> {
+        PrettySolution {
+            solution: self,
+            color,
+        }
+    }
+}
+
+/// Renders a `Solution` with colored part labels. Without color the output matches `Display`.
+#[cfg(feature = "std")]
+pub struct PrettySolution<'a, T: Display> {
+    solution: &'a Solution<T>,
+    color: bool,
+}
+
+#[cfg(feature = "std")]
+impl<T: Copy + Display> Display for PrettySolution<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.color {
+            return write!(f, "{}", self.solution);
+        }
+        let present = anstyle::Style::new().fg_color(Some(anstyle::AnsiColor::Green.into()));
+        let missing = anstyle::Style::new().dimmed();
+        write!(
+            f,
+            "{}part 1:{} {}",
+            present.render(),
+            present.render_reset(),
+            self.solution.part1
+        )?;
+        match self.solution.part2 {
+            Some(part2) => write!(
+                f,
+                "\n{}part 2:{} {}",
+                present.render(),
+                present.render_reset(),
+                part2
+            ),
+            None => write!(
+                f,
+                "\n{}part 2: unsolved{}",
+                missing.render(),
+                missing.render_reset()
+            ),
         }
-        Ok(())
     }
 }
 
+#[cfg(feature = "std")]
 pub trait Solver<T: Copy + Display> {
-    type Err;
-    fn solve(&self, input: &io::Source) -> Result<Solution<T>, Self::Err>;
+    type Err: From<std::io::Error>;
+
+    fn solve_str(&self, input: &str) -> Result<Solution<T>, Self::Err>;
+
+    /// The parsed input, pretty-printed, for days that parse it into a single value first.
+    fn dump_parsed(&self, _input: &str) -> Result<Option<String>, Self::Err> {
+        Ok(None)
+    }
+
+    fn solve(&self, input: &io::Source) -> Result<Solution<T>, Self::Err> {
+        self.solve_str(&input.read_string()?)
+    }
+
+    /// Solves from any reader, such as a socket or a decompressed stream.
+    fn solve_reader(&self, reader: &mut dyn std::io::Read) -> Result<Solution<T>, Self::Err> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        self.solve_str(&input)
+    }
 
     fn run(&self, input: &io::Source) -> Result<(), Self::Err> {
         let solution = self.solve(input)?;
@@ -75,3 +196,105 @@ pub fn error_free<T, E>(intermediate_results: Vec<Result<T, E>>) -> Result<Vec<T
     }
     Ok(results)
 }
+
+/// The whitespace-separated token that failed to parse as a number.
+#[derive(Debug, Error, PartialEq)]
+#[error("Invalid number: {0}")]
+pub struct ParseNumbersError(pub String);
+
+/// Parses a whitespace-separated list of numbers.
+pub fn parse_numbers<T: FromStr>(s: &str) -> Result<Vec<T>, ParseNumbersError> {
+    parse_numbers_with(s, str::parse)
+}
+
+/// Like `parse_numbers`, but parses each token with `parse`, e.g. to use a different radix.
+pub fn parse_numbers_with<T, E>(
+    s: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<Vec<T>, ParseNumbersError> {
+    s.split_whitespace()
+        .map(|token| parse(token).map_err(|_| ParseNumbersError(token.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_solution_parts() {
+        let solution = Solution::partial(1);
+        assert!(!solution.is_complete());
+        assert_eq!(solution.parts().collect::<Vec<_>>(), vec![(1, &1)]);
+    }
+
+    #[test]
+    fn test_complete_solution_parts() {
+        let solution = Solution::new(1, 2);
+        assert!(solution.is_complete());
+        assert_eq!(solution.parts().collect::<Vec<_>>(), vec![(1, &1), (2, &2)]);
+    }
+
+    #[test]
+    fn test_solution_from_parts() {
+        let solution = Solution::from_parts(1, Some(2));
+        assert_eq!(solution, Solution::new(1, 2));
+        assert_eq!(solution.to_string(), "part 1: 1\npart 2: 2");
+        let solution = Solution::from_parts(1, None);
+        assert_eq!(solution, Solution::partial(1));
+        assert_eq!(solution.to_string(), "part 1: 1");
+    }
+
+    #[test]
+    fn test_solution_equality() {
+        assert_eq!(Solution::new(1, 2), Solution::new(1, 2));
+        assert_eq!(Solution::partial(1), Solution::partial(1));
+        assert_ne!(Solution::new(1, 2), Solution::new(1, 3));
+        assert_ne!(Solution::new(1, 2), Solution::partial(1));
+    }
+
+    #[test]
+    fn test_format_inline() {
+        assert_eq!(
+            Solution::new(142, 281).format_inline(),
+            "part 1: 142 | part 2: 281"
+        );
+        assert_eq!(Solution::partial(142).format_inline(), "part 1: 142");
+    }
+
+    #[test]
+    fn test_pretty_without_color_matches_display() {
+        let solution = Solution::new(55029, 55686);
+        assert_eq!(solution.pretty(false).to_string(), solution.to_string());
+        let solution = Solution::partial(7);
+        assert_eq!(solution.pretty(false).to_string(), solution.to_string());
+    }
+
+    #[test]
+    fn test_pretty_with_color_styles_labels() {
+        let solution = Solution::partial(7);
+        let pretty = solution.pretty(true).to_string();
+        assert!(pretty.contains("\u{1b}["));
+        assert!(pretty.contains("part 1:"));
+        assert!(pretty.contains("part 2: unsolved"));
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(parse_numbers::<u32>(" 7  15\t30 "), Ok(vec![7, 15, 30]));
+        assert_eq!(parse_numbers::<u32>(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_numbers_reports_token() {
+        let error = parse_numbers::<u32>("7 1x5 30").unwrap_err();
+        assert_eq!(error, ParseNumbersError("1x5".to_string()));
+        assert_eq!(error.to_string(), "Invalid number: 1x5");
+    }
+
+    #[test]
+    fn test_parse_numbers_with_radix() {
+        let numbers = parse_numbers_with("ff 10", |n| u32::from_str_radix(n, 16));
+        assert_eq!(numbers, Ok(vec![255, 16]));
+    }
+}
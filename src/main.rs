@@ -1,12 +1,23 @@
-use advent_of_code_2023::days::{day1, day2, day3, day4, day5, day6, day7};
+use advent_of_code_2023::almanac::Almanac;
+use advent_of_code_2023::days::{day0, day1, day2, day3, day4, day5, day6, day7, DayListing, DAYS};
 use advent_of_code_2023::io::Source;
-use advent_of_code_2023::Solver;
-use anyhow::Context;
-use clap::Parser;
-use log::{info, Level};
+use advent_of_code_2023::timing::{solve_with_timeout, time_solve};
+use advent_of_code_2023::trebuchet::CalibrationValueReader;
+use advent_of_code_2023::{Solution, Solver};
+use anyhow::{bail, Context};
+use clap::{Parser, ValueEnum};
+use log::{error, info, Level};
+use std::fmt::Display;
+use std::io::{IsTerminal, Write};
 use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+#[cfg(feature = "dhat")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
 fn source_value_parser(value: &str) -> Result<Source, String> {
     match Source::try_from(value) {
         Ok(s) => Ok(s),
@@ -17,49 +28,339 @@ fn source_value_parser(value: &str) -> Result<Source, String> {
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
-    /// The day to run
-    #[arg(value_parser = day_in_range)]
-    day: u8,
-    /// The input file to use
-    #[arg(long, short, value_parser = source_value_parser, default_value = "-")]
-    input: Source,
+    /// The days to run. With more than one day, each reads `day-<day>.txt` from --input-dir
+    #[arg(value_parser = day_value_parser, required_unless_present = "list")]
+    days: Vec<Day>,
+    /// The directory holding each day's input when running more than one day
+    #[arg(long, default_value = "inputs")]
+    input_dir: PathBuf,
+    /// Keep running the remaining days after one fails, and fail at the end
+    #[arg(long)]
+    keep_going: bool,
+    /// List the implemented days and exit
+    #[arg(long)]
+    list: bool,
+    /// The input file to use for a single day (defaults to stdin, `env:NAME` reads a variable)
+    #[arg(long, short, value_parser = source_value_parser)]
+    input: Option<Source>,
+    /// Give up reading stdin after this many seconds without reaching the end
+    #[arg(long)]
+    input_timeout: Option<u64>,
     /// The log level to use
     #[arg(long, default_value = "info")]
     log_level: Level,
+    /// How to print each day's solution
+    #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
+    format: OutputFormat,
+    /// When to colorize the solution output
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+    /// Report how long the solve took
+    #[arg(long)]
+    time: bool,
+    /// Solve this many times and report min/median/max durations (requires --time)
+    #[arg(long, requires = "time")]
+    repeat: Option<usize>,
+    /// Print each day's parsed input to stderr before solving
+    #[arg(long, conflicts_with_all = ["time", "timeout"])]
+    dump_parsed: bool,
+    /// Give up on a solve that takes longer than this many seconds
+    #[arg(long, conflicts_with = "time")]
+    timeout: Option<u64>,
+    /// Cross-check day 5 part 2 by brute force when there are at most this many seeds
+    #[arg(long)]
+    max_brute_seeds: Option<usize>,
+    /// Show which digits were chosen on each line of day 1
+    #[arg(long, conflicts_with = "time")]
+    explain: bool,
+    /// Print each stage day 5's almanac maps this seed through on its way to a location
+    #[arg(long, conflicts_with = "time")]
+    trace_seed: Option<usize>,
+    /// Log extra per-day detail, such as each race's count on day 6
+    #[arg(long, short)]
+    verbose: bool,
+    /// Report peak heap usage and allocation count to stderr
+    #[cfg(feature = "dhat")]
+    #[arg(long, conflicts_with = "time")]
+    profile: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Plain,
+    /// One JSON object per day, e.g. `{"day":1,"part1":55029,"part2":55686,"millis":3}`
+    Jsonl,
+    /// An aligned table with `Part` and `Answer` columns
+    Table,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => std::io::stdout().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+fn seed_solver(cli: &Cli) -> day5::SeedSolver {
+    match cli.max_brute_seeds {
+        Some(max) => day5::SeedSolver::with_max_brute_seeds(max),
+        None => day5::SeedSolver::default(),
+    }
+}
+
+fn race_solver(cli: &Cli) -> day6::RaceSolver {
+    if cli.verbose {
+        day6::RaceSolver::verbose()
+    } else {
+        day6::RaceSolver::default()
+    }
+}
+
+fn day_input(cli: &Cli, day: Day) -> anyhow::Result<Source> {
+    let source = match (&cli.input, cli.days.len()) {
+        (Some(_), days) if days > 1 => bail!("--input can only be used with a single day"),
+        (Some(input), _) => input.clone(),
+        (None, 1) => Source::Stdin,
+        (None, _) => Source::File(cli.input_dir.join(format!("day-{}.txt", day.number()))),
+    };
+    match (&source, cli.input_timeout) {
+        (Source::Stdin, Some(timeout)) => Ok(Source::Memory(
+            source.read_string_timeout(Duration::from_secs(timeout))?,
+        )),
+        _ => Ok(source),
+    }
+}
+
+fn run<T, S>(
+    solver: &S,
+    cli: &Cli,
+    day: Day,
+    input: &Source,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> anyhow::Result<()>
+where
+    T: Copy + Display + Send + 'static,
+    S: Solver<T> + Clone + Send + 'static,
+    S::Err: std::error::Error + Send + Sync + 'static,
+{
+    if cli.dump_parsed {
+        return dump_and_solve(solver, cli, day, input, out, err);
+    }
+    #[cfg(feature = "dhat")]
+    if cli.profile {
+        let start = Instant::now();
+        let (solution, stats) = advent_of_code_2023::profiling::profile_solve(solver, input)?;
+        eprintln!("Allocations: {}", stats);
+        return write_solution(out, cli, day, &solution, start.elapsed());
+    }
+    let (solution, elapsed) = if cli.time {
+        let (solution, timings) = time_solve(solver, input, cli.repeat.unwrap_or(1))?;
+        info!("Timing: {}", timings);
+        (solution, timings.median().unwrap_or_default())
+    } else if let Some(timeout) = cli.timeout {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(timeout);
+        let solution = solve_with_timeout(solver.clone(), input.clone(), timeout)?;
+        (solution, start.elapsed())
+    } else {
+        let start = Instant::now();
+        let solution = solver.solve(input)?;
+        (solution, start.elapsed())
+    };
+    write_solution(out, cli, day, &solution, elapsed)
+}
+
+fn dump_and_solve<T, S>(
+    solver: &S,
+    cli: &Cli,
+    day: Day,
+    input: &Source,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> anyhow::Result<()>
+where
+    T: Copy + Display,
+    S: Solver<T>,
+    S::Err: std::error::Error + Send + Sync + 'static,
+{
+    let text = input.read_string()?;
+    match solver.dump_parsed(&text)? {
+        Some(parsed) => writeln!(err, "{}", parsed)?,
+        None => info!("Day {} has no parsed input to dump", day.number()),
+    }
+    let start = Instant::now();
+    let solution = solver.solve_str(&text)?;
+    write_solution(out, cli, day, &solution, start.elapsed())
 }
 
-fn print(input: &Source) -> anyhow::Result<()> {
-    info!("Reading input from {}", input);
-    let input = input
-        .read_string()
-        .with_context(|| format!("Failed to read input: {input}"))?;
-    println!("{}", input);
+fn write_solution<T: Copy + Display>(
+    out: &mut dyn Write,
+    cli: &Cli,
+    day: Day,
+    solution: &Solution<T>,
+    elapsed: Duration,
+) -> anyhow::Result<()> {
+    match cli.format {
+        OutputFormat::Plain => writeln!(out, "{}", solution.pretty(cli.color.enabled()))?,
+        OutputFormat::Jsonl => writeln!(out, "{}", json_line(day, solution, elapsed))?,
+        OutputFormat::Table => write!(out, "{}", table(solution))?,
+    }
     Ok(())
 }
 
-const DAY_RANGE: RangeInclusive<usize> = 0..=7;
+fn json_line<T: Copy + Display>(day: Day, solution: &Solution<T>, elapsed: Duration) -> String {
+    let part2 = solution
+        .part2()
+        .map_or_else(|| "null".to_string(), |part2| part2.to_string());
+    format!(
+        r#"{{"day":{},"part1":{},"part2":{},"millis":{}}}"#,
+        day.number(),
+        solution.part1(),
+        part2,
+        elapsed.as_millis()
+    )
+}
+
+fn table<T: Copy + Display>(solution: &Solution<T>) -> String {
+    const HEADERS: [&str; 2] = ["Part", "Answer"];
+    let rows = solution
+        .parts()
+        .map(|(part, answer)| [part.to_string(), answer.to_string()])
+        .collect::<Vec<_>>();
+    let widths: [usize; 2] = std::array::from_fn(|column| {
+        rows.iter()
+            .map(|row| row[column].len())
+            .chain([HEADERS[column].len()])
+            .max()
+            .unwrap_or_default()
+    });
+    let mut table = String::new();
+    for [part, answer] in std::iter::once(HEADERS.map(String::from)).chain(rows) {
+        table += &format!(
+            "{:>w0$}  {:>w1$}\n",
+            part,
+            answer,
+            w0 = widths[0],
+            w1 = widths[1]
+        );
+    }
+    table
+}
+
+fn explain_day1(cli: &Cli, day: Day, input: &Source, out: &mut dyn Write) -> anyhow::Result<()> {
+    let text = input.read_string()?;
+    write!(out, "{}", CalibrationValueReader::default().explain(&text)?)?;
+    let start = Instant::now();
+    let solution = day1::CalibrationSolver.solve_str(&text)?;
+    write_solution(out, cli, day, &solution, start.elapsed())
+}
+
+fn trace_day5(cli: &Cli, day: Day, input: &Source, out: &mut dyn Write) -> anyhow::Result<()> {
+    let text = input.read_string()?;
+    if let Some(seed) = cli.trace_seed {
+        writeln!(
+            out,
+            "{}",
+            format_trace(&text.parse::<Almanac>()?.trace(seed))
+        )?;
+    }
+    let start = Instant::now();
+    let solution = seed_solver(cli).solve_str(&text)?;
+    write_solution(out, cli, day, &solution, start.elapsed())
+}
+
+/// Formats an almanac trace as `seed 79 -> soil 81 -> ... -> location 82`.
+fn format_trace(trace: &[(&str, usize)]) -> String {
+    trace
+        .iter()
+        .map(|(label, value)| format!("{} {}", label, value))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Runs a single day, writing its solution to `out` and any `--dump-parsed` output to `err`.
+fn run_day(
+    day: Day,
+    cli: &Cli,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> Result<(), ApplicationError> {
+    let input = day_input(cli, day)?;
+    match day.number() {
+        0 => run(&day0::EchoSolver, cli, day, &input, out, err).with_context(|| "Day 0 failed")?,
+        1 if cli.explain => explain_day1(cli, day, &input, out).with_context(|| "Day 1 failed")?,
+        1 => run(&day1::CalibrationSolver, cli, day, &input, out, err)
+            .with_context(|| "Day 1 failed")?,
+        2 => run(&day2::GameSolver::default(), cli, day, &input, out, err)
+            .with_context(|| "Day 2 failed")?,
+        3 => run(&day3::GearRatioSolver, cli, day, &input, out, err)
+            .with_context(|| "Day 3 failed")?,
+        4 => run(&day4::ScratchCardSolver, cli, day, &input, out, err)
+            .with_context(|| "Day 4 failed")?,
+        5 if cli.trace_seed.is_some() => {
+            trace_day5(cli, day, &input, out).with_context(|| "Day 5 failed")?
+        }
+        5 => run(&seed_solver(cli), cli, day, &input, out, err).with_context(|| "Day 5 failed")?,
+        6 => run(&race_solver(cli), cli, day, &input, out, err).with_context(|| "Day 6 failed")?,
+        7 => run(&day7::Day7Solver, cli, day, &input, out, err).with_context(|| "Day 7 failed")?,
+        other => return Err(ApplicationError::InvalidDay(other)),
+    };
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Day(u8);
+
+impl Day {
+    const RANGE: RangeInclusive<u8> = 0..=7;
+
+    fn number(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for Day {
+    type Error = ApplicationError;
 
-fn day_in_range(value: &str) -> Result<u8, String> {
-    let day: usize = value
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        if Day::RANGE.contains(&day) {
+            Ok(Day(day))
+        } else {
+            Err(ApplicationError::InvalidDay(day))
+        }
+    }
+}
+
+fn day_value_parser(value: &str) -> Result<Day, String> {
+    let day: u8 = value
         .parse()
         .map_err(|e| format!("Invalid day: {} ({})", value, e))?;
-    if DAY_RANGE.contains(&day) {
-        Ok(day as u8)
-    } else {
-        Err(format!(
-            "Invalid day: {}. Must be in the range {}-{}",
-            day,
-            DAY_RANGE.start(),
-            DAY_RANGE.end()
-        ))
-    }
+    Day::try_from(day).map_err(|e| e.to_string())
 }
 
 #[derive(Debug, Error)]
 pub enum ApplicationError {
-    #[error("Invalid day: {0}")]
+    #[error(
+        "Invalid day: {0}. Must be in the range {start}-{end}",
+        start = Day::RANGE.start(),
+        end = Day::RANGE.end()
+    )]
     InvalidDay(u8),
 
+    #[error("Failed days: {0:?}")]
+    DaysFailed(Vec<u8>),
+
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
@@ -67,32 +368,30 @@ pub enum ApplicationError {
 fn main() -> Result<(), ApplicationError> {
     let cli = Cli::parse();
     simple_logger::init_with_level(cli.log_level).context("Failed to initialize logger")?;
-    match cli.day {
-        0 => print(&cli.input)?,
-        1 => day1::CalibrationSolver
-            .run(&cli.input)
-            .with_context(|| "Day 1 failed")?,
-        2 => day2::GameSolver::default()
-            .run(&cli.input)
-            .with_context(|| "Day 2 failed")?,
-        3 => day3::GearRatioSolver
-            .run(&cli.input)
-            .with_context(|| "Day 3 failed")?,
-        4 => day4::ScratchCardSolver
-            .run(&cli.input)
-            .with_context(|| "Day 4 failed")?,
-        5 => day5::SeedSolver
-            .run(&cli.input)
-            .with_context(|| "Day 5 failed")?,
-        6 => day6::RaceSolver
-            .run(&cli.input)
-            .with_context(|| "Day 6 failed")?,
-        7 => day7::Day7Solver
-            .run(&cli.input)
-            .with_context(|| "Day 7 failed")?,
-        _ => return Err(ApplicationError::InvalidDay(cli.day)),
-    };
-    Ok(())
+    if cli.list {
+        print!("{}", DayListing(&DAYS));
+        return Ok(());
+    }
+    run_days(&cli, &mut std::io::stdout().lock(), &mut std::io::stderr())
+}
+
+fn run_days(cli: &Cli, out: &mut dyn Write, err: &mut dyn Write) -> Result<(), ApplicationError> {
+    let mut failed = Vec::new();
+    for day in &cli.days {
+        match run_day(*day, cli, out, err) {
+            Ok(()) => {}
+            Err(e) if cli.keep_going => {
+                error!("{:#}", anyhow::Error::from(e));
+                failed.push(day.number());
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(ApplicationError::DaysFailed(failed))
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +403,161 @@ mod tests {
         use clap::CommandFactory;
         Cli::command().debug_assert()
     }
+
+    #[test]
+    fn test_jsonl_one_line_per_day() {
+        let cli = Cli::parse_from(["advent-of-code-2023", "1", "6", "--format", "jsonl"]);
+        let mut out = Vec::new();
+        for day in &cli.days {
+            run_day(*day, &cli, &mut out, &mut std::io::sink()).unwrap();
+        }
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["day"], 1);
+        assert_eq!(first["part1"], 55029);
+        assert_eq!(first["part2"], 55686);
+        assert!(first["millis"].is_u64());
+        let second: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second["day"], 6);
+    }
+
+    #[test]
+    fn test_table_aligns_columns() {
+        let solution = Solution::new(142, 281_000_000);
+        assert_eq!(
+            table(&solution),
+            "Part     Answer\n   1        142\n   2  281000000\n"
+        );
+    }
+
+    #[test]
+    fn test_table_partial_solution() {
+        assert_eq!(table(&Solution::partial(7)), "Part  Answer\n   1       7\n");
+    }
+
+    #[test]
+    fn test_dump_parsed_day_2() {
+        let cli = Cli::parse_from([
+            "advent-of-code-2023",
+            "2",
+            "--dump-parsed",
+            "--format",
+            "jsonl",
+            "--input",
+            "inputs/day-2.txt",
+        ]);
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        run_day(Day(2), &cli, &mut out, &mut err).unwrap();
+        let err = String::from_utf8(err).unwrap();
+        assert!(err.starts_with("Games {"));
+        assert!(err.contains("id: 100"));
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with(r#"{"day":2,"part1":2085,"part2":79315,"#));
+    }
+
+    #[test]
+    fn test_trace_seed_example() {
+        let cli = Cli::parse_from([
+            "advent-of-code-2023",
+            "5",
+            "--trace-seed",
+            "79",
+            "--input",
+            "inputs/day-5-example.txt",
+        ]);
+        let mut out = Vec::new();
+        run_day(Day(5), &cli, &mut out, &mut std::io::sink()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out.lines().next(),
+            Some("seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> humidity 78 -> location 82")
+        );
+    }
+
+    /// A temporary input directory holding only day 1's input, removed when dropped. Each test
+    /// passes its own `name` so that tests running in parallel don't share a directory.
+    struct InputDirWithOnlyDay1(PathBuf);
+
+    impl InputDirWithOnlyDay1 {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+            // Clear out anything left behind by an earlier run that was killed.
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::copy("inputs/day-1.txt", dir.join("day-1.txt")).unwrap();
+            InputDirWithOnlyDay1(dir)
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for InputDirWithOnlyDay1 {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_fail_fast_stops_at_first_failure() {
+        let dir = InputDirWithOnlyDay1::new("fail-fast");
+        let cli = Cli::parse_from([
+            "advent-of-code-2023",
+            "2",
+            "1",
+            "--format",
+            "jsonl",
+            "--input-dir",
+            dir.path(),
+        ]);
+        let mut out = Vec::new();
+        let error = run_days(&cli, &mut out, &mut std::io::sink()).unwrap_err();
+        assert!(matches!(error, ApplicationError::Other(_)));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_keep_going_runs_remaining_days() {
+        let dir = InputDirWithOnlyDay1::new("keep-going");
+        let cli = Cli::parse_from([
+            "advent-of-code-2023",
+            "2",
+            "1",
+            "--format",
+            "jsonl",
+            "--keep-going",
+            "--input-dir",
+            dir.path(),
+        ]);
+        let mut out = Vec::new();
+        let error = run_days(&cli, &mut out, &mut std::io::sink()).unwrap_err();
+        assert!(matches!(error, ApplicationError::DaysFailed(ref days) if days == &[2]));
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), 1);
+        assert!(out.starts_with(r#"{"day":1,"#));
+    }
+
+    #[test]
+    fn test_input_rejected_for_multiple_days() {
+        let cli = Cli::parse_from(["advent-of-code-2023", "1", "6", "--input", "-"]);
+        assert!(day_input(&cli, Day(1)).is_err());
+    }
+
+    #[test]
+    fn test_day_try_from_in_range() {
+        assert_eq!(Day::try_from(5).unwrap().number(), 5);
+    }
+
+    #[test]
+    fn test_day_try_from_out_of_range() {
+        let error = Day::try_from(8).unwrap_err();
+        assert!(matches!(error, ApplicationError::InvalidDay(8)));
+        assert_eq!(
+            error.to_string(),
+            "Invalid day: 8. Must be in the range 0-7"
+        );
+    }
 }
@@ -0,0 +1,59 @@
+use crate::io::Source;
+use crate::{Solution, Solver};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Heap usage of a single solve, as recorded by `dhat`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AllocationStats {
+    pub peak_bytes: usize,
+    pub total_allocations: u64,
+}
+
+impl Display for AllocationStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "peak {} bytes, {} allocation(s)",
+            self.peak_bytes, self.total_allocations
+        )
+    }
+}
+
+/// Solves under a `dhat` profiler. Counts are only non-zero when the binary
+/// installs `dhat::Alloc` as its global allocator.
+pub fn profile_solve<T, S>(
+    solver: &S,
+    input: &Source,
+) -> Result<(Solution<T>, AllocationStats), S::Err>
+where
+    T: Copy + Display,
+    S: Solver<T>,
+{
+    // Testing mode keeps the stats queryable and skips writing `dhat-heap.json`.
+    let _profiler = dhat::Profiler::builder().testing().build();
+    let solution = solver.solve(input)?;
+    let stats = dhat::HeapStats::get();
+    Ok((
+        solution,
+        AllocationStats {
+            peak_bytes: stats.max_bytes,
+            total_allocations: stats.total_blocks,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day1::{CalibrationSolver, CalibrationSolverError};
+
+    #[test]
+    fn test_profile_solve_day1() -> Result<(), CalibrationSolverError> {
+        let input = Source::try_from("inputs/day-1.txt")?;
+        let (solution, stats) = profile_solve(&CalibrationSolver, &input)?;
+        assert_eq!(solution.part1(), 55029);
+        assert!(stats.to_string().starts_with("peak "));
+        Ok(())
+    }
+}
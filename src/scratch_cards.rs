@@ -1,7 +1,25 @@
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use crate::parse_numbers_with;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::iter::Sum;
+use core::str::FromStr;
 use thiserror::Error;
 
+/// Points for each match count a real card can have (up to ten winning numbers).
+const POINTS: [u32; 11] = points_table();
+
+const fn points_table() -> [u32; 11] {
+    let mut table = [0; 11];
+    let mut matches = 1;
+    while matches < table.len() {
+        table[matches] = 1 << (matches - 1);
+        matches += 1;
+    }
+    table
+}
+
 #[derive(Debug)]
 pub struct Card {
     id: u32,
@@ -24,51 +42,20 @@ pub enum CardParseError {
     InvalidWinningNumber(String),
     #[error("Invalid input: invalid scratch number: {0}")]
     InvalidScratchNumber(String),
+    #[error("Invalid input: duplicate card id: {0}")]
+    DuplicateCardId(u32),
 }
 
 impl FromStr for Card {
     type Err = CardParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (card, numbers) = s.split_once(": ").ok_or(CardParseError::MissingColon)?;
-        if !card.starts_with("Card ") {
-            return Err(CardParseError::MissingCardPrefix);
-        }
-        let card_id = card[5..]
-            .trim()
-            .parse::<u32>()
-            .map_err(|_| CardParseError::InvalidCardId(card[5..].to_string()))?;
-
-        let (winning_numbers, scratched_numbers) = numbers
-            .split_once(" | ")
-            .ok_or(CardParseError::MissingVerticalBar)?;
-
-        let winning_numbers = winning_numbers.trim();
-        let scratched_numbers = scratched_numbers.trim();
-
-        let winning_numbers = winning_numbers
-            .split_whitespace()
-            .map(|n| {
-                n.parse::<u32>()
-                    .map_err(|_| CardParseError::InvalidWinningNumber(n.to_string()))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        //let winning_numbers = error_free(winning_numbers)?;
-        let scratched_numbers = scratched_numbers
-            .split_whitespace()
-            .map(|n| {
-                n.parse::<u32>()
-                    .map_err(|_| CardParseError::InvalidScratchNumber(n.to_string()))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(Card::new(card_id, winning_numbers, scratched_numbers))
+        Card::from_str_radix(s, 10)
     }
 }
 
 impl Display for Card {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "Card {}: ", self.id)?;
         for (i, number) in self.winning_numbers.iter().enumerate() {
             if i > 0 {
@@ -88,8 +75,39 @@ impl Display for Card {
 }
 
 impl Card {
+    /// Parses a card whose numbers are written in `radix`. The card id is always decimal.
+    ///
+    /// Panics if `radix` is not in the range 2 to 36, as `u32::from_str_radix` does.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, CardParseError> {
+        let (card, numbers) = s.split_once(": ").ok_or(CardParseError::MissingColon)?;
+        if !card.starts_with("Card ") {
+            return Err(CardParseError::MissingCardPrefix);
+        }
+        let card_id = card[5..]
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| CardParseError::InvalidCardId(card[5..].to_string()))?;
+
+        let (winning_numbers, scratched_numbers) = numbers
+            .split_once(" | ")
+            .ok_or(CardParseError::MissingVerticalBar)?;
+
+        let winning_numbers = winning_numbers.trim();
+        let scratched_numbers = scratched_numbers.trim();
+
+        let parse = |n: &str| u32::from_str_radix(n, radix);
+        let winning_numbers = parse_numbers_with(winning_numbers, parse)
+            .map_err(|e| CardParseError::InvalidWinningNumber(e.0))?;
+        let scratched_numbers = parse_numbers_with(scratched_numbers, parse)
+            .map_err(|e| CardParseError::InvalidScratchNumber(e.0))?;
+
+        Ok(Card::new(card_id, winning_numbers, scratched_numbers))
+    }
+
     pub fn new(id: u32, winning_numbers: Vec<u32>, scratched_numbers: Vec<u32>) -> Self {
-        let matching_numbers = Self::find_matching_numbers(&winning_numbers, &scratched_numbers);
+        let mut matching_numbers =
+            Self::find_matching_numbers(&winning_numbers, &scratched_numbers);
+        matching_numbers.sort_unstable();
         Card {
             id,
             winning_numbers,
@@ -108,18 +126,63 @@ impl Card {
         matching_numbers
     }
 
+    /// The winning numbers that were scratched, in ascending order.
+    pub fn matching_numbers(&self) -> &[u32] {
+        &self.matching_numbers
+    }
+
     pub fn total_matches(&self) -> usize {
         self.matching_numbers.len()
     }
 
+    pub fn matches(&self) -> usize {
+        self.total_matches()
+    }
+
+    /// Points as `2^(matches - 1)`, saturating at `u32::MAX`.
     pub fn get_points(&self) -> u32 {
-        let matches = self.total_matches();
-        if matches == 0 {
-            0
-        } else {
-            2_u32.pow((matches - 1) as u32)
+        match POINTS.get(self.total_matches()) {
+            Some(points) => *points,
+            None => self.points_checked().unwrap_or(u32::MAX),
+        }
+    }
+
+    /// Points as `2^(matches - 1)`, or `None` if that does not fit in a `u32`.
+    pub fn points_checked(&self) -> Option<u32> {
+        match self.total_matches() {
+            0 => Some(0),
+            matches => 2_u32.checked_pow(u32::try_from(matches - 1).ok()?),
         }
     }
+
+    /// Points widened to `u64`, saturating at `u64::MAX` beyond 64 matches.
+    pub fn points_u64(&self) -> u64 {
+        match self.total_matches() {
+            0 => 0,
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|exp| 2_u64.checked_pow(exp))
+                .unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// A total of scratchcard points. Sums saturate at `u32::MAX`, as a single card's points do.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Points(pub u32);
+
+impl Sum for Points {
+    fn sum<I: Iterator<Item = Points>>(iter: I) -> Self {
+        iter.fold(Points(0), |total, points| {
+            Points(total.0.saturating_add(points.0))
+        })
+    }
+}
+
+impl Display for Points {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 #[derive(Debug)]
@@ -141,8 +204,8 @@ impl CardCopies {
         self.card.total_matches()
     }
 
-    fn get_points(&self) -> u32 {
-        self.card.get_points()
+    fn get_points(&self) -> Points {
+        Points(self.card.points_checked().unwrap_or(u32::MAX))
     }
 }
 
@@ -159,13 +222,18 @@ pub struct CardSet {
 }
 
 impl CardSet {
-    pub fn new(cards: Vec<Card>) -> Self {
+    /// Copies are won by position, so every card id must be unique.
+    pub fn new(cards: Vec<Card>) -> Result<Self, CardParseError> {
+        let mut ids = BTreeSet::new();
+        if let Some(card) = cards.iter().find(|card| !ids.insert(card.id)) {
+            return Err(CardParseError::DuplicateCardId(card.id));
+        }
         let mut card_set = CardSet {
             cards: cards.into_iter().map(|c| c.into()).collect(),
             resolved: false,
         };
         card_set.resolve();
-        card_set
+        Ok(card_set)
     }
 
     pub fn is_empty(&self) -> bool {
@@ -206,13 +274,56 @@ impl CardSet {
         }
     }
 
+    /// For each card id, the ids of the following cards it wins copies of, one per match.
+    /// Grants past the end of the set are dropped, as in `total_instances`.
+    pub fn copy_trace(&self) -> Vec<(u32, Vec<u32>)> {
+        self.cards
+            .iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let granted = self.cards[i + 1..]
+                    .iter()
+                    .take(card.total_matches())
+                    .map(|copy| copy.card.id)
+                    .collect();
+                (card.card.id, granted)
+            })
+            .collect()
+    }
+
     pub fn total_matches(&self) -> usize {
         self.cards.iter().map(|card| card.total_matches()).sum()
     }
 
-    pub fn get_points(&self) -> u32 {
+    pub fn get_points(&self) -> Points {
         self.cards.iter().map(|card| card.get_points()).sum()
     }
+
+    /// Sum of each card's points multiplied by its instance count, saturating at `u64::MAX`.
+    pub fn weighted_points(&self) -> u64 {
+        self.cards
+            .iter()
+            .map(|card| {
+                card.card
+                    .points_u64()
+                    .saturating_mul(card.instances() as u64)
+            })
+            .fold(0, u64::saturating_add)
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = (Card, u32);
+    type IntoIter =
+        core::iter::Map<alloc::vec::IntoIter<CardCopies>, fn(CardCopies) -> (Card, u32)>;
+
+    /// Yields each resolved card with its instance count.
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter().map(|copies| {
+            let instances = copies.instances();
+            (copies.card, instances)
+        })
+    }
 }
 
 impl FromStr for CardSet {
@@ -221,16 +332,32 @@ impl FromStr for CardSet {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cards = s
             .lines()
+            .filter(|line| !line.trim().is_empty())
             .map(|line| line.parse::<Card>())
             .collect::<Result<Vec<_>, _>>()?;
-        Ok(CardSet::new(cards))
+        CardSet::new(cards)
     }
 }
 
+/// Sums the points of each card in `input` without resolving copies, matching
+/// `CardSet::get_points`.
+pub fn sum_points(input: &str) -> Result<Points, CardParseError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let card = line.parse::<Card>()?;
+            Ok(Points(card.points_checked().unwrap_or(u32::MAX)))
+        })
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\nCard 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\nCard 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\nCard 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\nCard 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
     #[test]
     fn test_parse_card() {
         let card = "Card 1: 1 2 3 4 5 | 6 7 8 9 10";
@@ -248,6 +375,14 @@ mod tests {
         assert_eq!(matching_numbers, vec![48, 83, 86, 17]);
     }
 
+    #[test]
+    fn test_matching_numbers_sorted() {
+        let card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+            .parse::<Card>()
+            .unwrap();
+        assert_eq!(card.matching_numbers(), &[17, 48, 83, 86]);
+    }
+
     #[test]
     fn test_sample_input_points() {
         let card_1 = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
@@ -293,5 +428,167 @@ mod tests {
         assert_eq!(card_copies, vec![1, 2, 4, 8, 14, 1]);
         let total_instances = card_set.total_instances();
         assert_eq!(30, total_instances);
+
+        let trace = card_set.copy_trace();
+        assert_eq!(trace[0], (1, vec![2, 3, 4, 5]));
+        assert_eq!(trace[4], (5, vec![]));
+        // Each card's instances are one original plus the instances of every card granting it.
+        for (id, instances) in card_set.cards.iter().map(|c| (c.card.id, c.instances())) {
+            let won = trace
+                .iter()
+                .filter(|(_, granted)| granted.contains(&id))
+                .map(|(granter, _)| card_set.cards[*granter as usize - 1].instances())
+                .sum::<u32>();
+            assert_eq!(instances, won + 1);
+        }
+    }
+
+    fn card_with_matches(id: u32, matches: u32) -> Card {
+        let numbers = (1..=matches).collect::<Vec<_>>();
+        Card::new(id, numbers.clone(), numbers)
+    }
+
+    #[test]
+    fn test_points_table_matches_formula() {
+        for matches in 0..=20 {
+            let expected = match matches {
+                0 => 0,
+                matches => 2_u32.pow(matches - 1),
+            };
+            assert_eq!(card_with_matches(1, matches).get_points(), expected);
+        }
+    }
+
+    #[test]
+    fn test_points_checked_overflow_boundary() {
+        assert_eq!(card_with_matches(1, 0).points_checked(), Some(0));
+        assert_eq!(card_with_matches(1, 32).points_checked(), Some(1 << 31));
+        assert_eq!(card_with_matches(1, 33).points_checked(), None);
+    }
+
+    #[test]
+    fn test_points_u64_overflow_boundary() {
+        assert_eq!(card_with_matches(1, 33).points_u64(), 1 << 32);
+        assert_eq!(card_with_matches(1, 64).points_u64(), 1 << 63);
+        assert_eq!(card_with_matches(1, 65).points_u64(), u64::MAX);
+    }
+
+    #[test]
+    fn test_card_set_duplicate_card_id() {
+        let result = CardSet::new(vec![
+            card_with_matches(2, 1),
+            card_with_matches(3, 1),
+            card_with_matches(3, 2),
+        ]);
+        assert_eq!(result.err(), Some(CardParseError::DuplicateCardId(3)));
+        let input = "Card 3: 1 2 | 1 3\nCard 3: 4 5 | 6 7";
+        assert_eq!(
+            input.parse::<CardSet>().err(),
+            Some(CardParseError::DuplicateCardId(3))
+        );
+    }
+
+    #[test]
+    fn test_card_set_points_saturate() {
+        let card_set =
+            CardSet::new(vec![card_with_matches(1, 32), card_with_matches(2, 33)]).unwrap();
+        assert_eq!(card_set.get_points(), Points(u32::MAX));
+    }
+
+    #[test]
+    fn test_card_display_round_trip() {
+        let original = "Card  3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"
+            .parse::<Card>()
+            .unwrap();
+        let formatted = original.to_string();
+        assert_eq!(formatted, "Card 3: 1 21 53 59 44 | 69 82 63 72 16 21 14 1");
+        let reparsed = formatted.parse::<Card>().unwrap();
+        assert_eq!(reparsed.id, original.id);
+        assert_eq!(reparsed.winning_numbers, original.winning_numbers);
+        assert_eq!(reparsed.scratched_numbers, original.scratched_numbers);
+    }
+
+    #[test]
+    fn test_card_display_round_trip_without_numbers() {
+        let original = Card::new(7, vec![], vec![]);
+        let reparsed = original.to_string().parse::<Card>().unwrap();
+        assert_eq!(reparsed.id, 7);
+        assert!(reparsed.winning_numbers.is_empty());
+        assert!(reparsed.scratched_numbers.is_empty());
+    }
+
+    #[test]
+    fn test_parse_card_radix_16() {
+        let card = Card::from_str_radix("Card 1: a b | c a", 16).unwrap();
+        assert_eq!(card.winning_numbers, vec![10, 11]);
+        assert_eq!(card.scratched_numbers, vec![12, 10]);
+        assert_eq!(card.matching_numbers, vec![10]);
+        assert_eq!(card.get_points(), 1);
+    }
+
+    #[test]
+    fn test_parse_card_radix_10_rejects_hex() {
+        let result = "Card 1: a b | c a".parse::<Card>();
+        assert_eq!(
+            result.err(),
+            Some(CardParseError::InvalidWinningNumber("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_card_set_into_iter_instances() {
+        let card_set = EXAMPLE_INPUT.parse::<CardSet>().unwrap();
+        let total_instances = card_set.total_instances();
+        let cards = card_set.into_iter().collect::<Vec<_>>();
+        assert_eq!(cards.len(), 6);
+        assert_eq!(cards[0].0.id, 1);
+        assert_eq!(cards[3].1, 8);
+        assert_eq!(
+            cards.iter().map(|(_, instances)| instances).sum::<u32>(),
+            total_instances
+        );
+        assert_eq!(total_instances, 30);
+    }
+
+    #[test]
+    fn test_card_set_weighted_points() {
+        let card_set = EXAMPLE_INPUT.parse::<CardSet>().unwrap();
+        assert_eq!(card_set.weighted_points(), 8 + 2 * 2 + 2 * 4 + 8);
+    }
+
+    #[test]
+    fn test_points_sum_and_display() {
+        let total = vec![Points(8), Points(2), Points(2), Points(1)]
+            .into_iter()
+            .sum::<Points>();
+        assert_eq!(total, Points(13));
+        assert_eq!(total.to_string(), "13");
+        assert_eq!(
+            [Points(u32::MAX), Points(1)].into_iter().sum::<Points>(),
+            Points(u32::MAX)
+        );
+        assert_eq!(core::iter::empty().sum::<Points>(), Points(0));
+    }
+
+    #[test]
+    fn test_sum_points_matches_card_set() {
+        assert_eq!(sum_points(EXAMPLE_INPUT), Ok(Points(13)));
+        assert_eq!(
+            sum_points(EXAMPLE_INPUT).unwrap(),
+            EXAMPLE_INPUT.parse::<CardSet>().unwrap().get_points()
+        );
+    }
+
+    #[test]
+    fn test_parse_card_set_skips_blank_lines() {
+        let expected = EXAMPLE_INPUT.parse::<CardSet>().unwrap();
+        let trailing = format!("{}\n", EXAMPLE_INPUT);
+        let interior = EXAMPLE_INPUT.replacen('\n', "\n\n", 2);
+        for padded in [trailing, interior] {
+            let card_set = padded.parse::<CardSet>().unwrap();
+            assert_eq!(card_set.get_points(), expected.get_points());
+            assert_eq!(card_set.total_instances(), expected.total_instances());
+            assert_eq!(sum_points(&padded), Ok(Points(13)));
+        }
     }
 }
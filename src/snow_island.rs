@@ -1,16 +1,23 @@
-use std::cmp::max;
-use std::fmt;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::max;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 use thiserror::Error;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Game {
     id: i32,
     results: Vec<GrabResult>,
 }
 
 impl Game {
+    pub fn new(id: i32, results: Vec<GrabResult>) -> Self {
+        Game { id, results }
+    }
+
     pub fn id(&self) -> i32 {
         self.id
     }
@@ -30,6 +37,54 @@ impl Display for Game {
     }
 }
 
+#[derive(Debug)]
+pub struct Games {
+    games: Vec<Game>,
+}
+
+impl Games {
+    pub fn new(games: Vec<Game>) -> Self {
+        Games { games }
+    }
+
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// Sums the ids of the games that `bag` could have produced.
+    pub fn possible_ids_sum(&self, bag: &GameBag) -> i32 {
+        self.games
+            .iter()
+            .filter(|game| bag.is_game_possible(game))
+            .map(Game::id)
+            .sum()
+    }
+
+    /// Sums the power of the smallest bag able to produce each game.
+    pub fn minimum_bag_power_sum(&self) -> i32 {
+        self.games
+            .iter()
+            .map(|game| GameBag::minimum_for(game).power())
+            .sum()
+    }
+}
+
+impl FromStr for Games {
+    type Err = GameParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let games = s
+            .lines()
+            .map(|line| line.parse::<Game>())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Games { games })
+    }
+}
+
 #[derive(Debug, Error, PartialEq)]
 pub enum GameParseError {
     #[error("Invalid input: missing colon")]
@@ -66,32 +121,32 @@ impl FromStr for Game {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GrabResult {
     blue: u32,
     red: u32,
     green: u32,
 }
 
+impl GrabResult {
+    /// The cube counts in display order: blue, red, then green.
+    pub fn colors_in_order(&self) -> Vec<(&'static str, u32)> {
+        vec![
+            ("blue", self.blue),
+            ("red", self.red),
+            ("green", self.green),
+        ]
+    }
+}
+
 impl Display for GrabResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut subsequent = false;
-        if self.blue > 0 {
-            write!(f, "{} blue", self.blue)?;
-            subsequent = true;
-        }
-        if self.red > 0 {
-            if subsequent {
-                write!(f, ", ")?;
-            }
-            write!(f, "{} red", self.red)?;
-            subsequent = true;
-        }
-        if self.green > 0 {
-            if subsequent {
+        let colors = self.colors_in_order();
+        for (i, (color, count)) in colors.iter().filter(|(_, c)| *c > 0).enumerate() {
+            if i > 0 {
                 write!(f, ", ")?;
             }
-            write!(f, "{} green", self.green)?;
+            write!(f, "{} {}", count, color)?;
         }
         Ok(())
     }
@@ -120,7 +175,7 @@ impl FromStr for GrabResult {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct GameBag {
     red: u32,
     green: u32,
@@ -150,6 +205,15 @@ impl GameBag {
         })
     }
 
+    pub fn covering(games: &[Game]) -> GameBag {
+        games
+            .iter()
+            .flat_map(|game| game.results.iter())
+            .fold(GameBag::empty(), |bag, result| {
+                bag.into_bag_satisfying_result(result)
+            })
+    }
+
     pub fn is_outcome_possible(&self, outcome: &GrabResult) -> bool {
         self.blue >= outcome.blue && self.red >= outcome.red && self.green >= outcome.green
     }
@@ -163,15 +227,59 @@ impl GameBag {
         true
     }
 
+    /// The bag left after drawing `grab` without replacement, or `None` if it holds too few cubes.
+    pub fn remaining_after(&self, grab: &GrabResult) -> Option<GameBag> {
+        Some(GameBag {
+            red: self.red.checked_sub(grab.red)?,
+            green: self.green.checked_sub(grab.green)?,
+            blue: self.blue.checked_sub(grab.blue)?,
+        })
+    }
+
+    /// Multiplies the count of every color by `factor`, saturating at `u32::MAX`.
+    pub fn scale(&self, factor: u32) -> GameBag {
+        GameBag {
+            red: self.red.saturating_mul(factor),
+            green: self.green.saturating_mul(factor),
+            blue: self.blue.saturating_mul(factor),
+        }
+    }
+
     pub fn power(&self) -> i32 {
         self.blue as i32 * self.red as i32 * self.green as i32
     }
 }
 
+/// Formats every color, including empty ones, in the same form `FromStr` accepts.
+impl Display for GameBag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} red, {} green, {} blue",
+            self.red, self.green, self.blue
+        )
+    }
+}
+
+impl FromStr for GameBag {
+    type Err = GameParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let contents = s.parse::<GrabResult>()?;
+        Ok(GameBag::empty().into_bag_satisfying_result(&contents))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
     #[test]
     fn test_parse_grab_result() {
         let input = "3 blue, 4 red";
@@ -181,6 +289,20 @@ mod tests {
         assert_eq!(result.green, 0);
     }
 
+    #[test]
+    fn test_parse_grab_result_invalid_color() {
+        let error = "3 purple".parse::<GrabResult>().unwrap_err();
+        assert_eq!(error, GameParseError::InvalidColor("purple".to_string()));
+        assert!(error.to_string().contains("purple"));
+    }
+
+    #[test]
+    fn test_parse_grab_result_invalid_count() {
+        let error = "x red".parse::<GrabResult>().unwrap_err();
+        assert_eq!(error, GameParseError::InvalidCount("x".to_string()));
+        assert!(error.to_string().contains("invalid count: x"));
+    }
+
     #[test]
     fn test_parse_game() {
         let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
@@ -198,6 +320,19 @@ mod tests {
         assert_eq!(result.results[2].green, 2);
     }
 
+    #[test]
+    fn test_new_game_matches_parsed() {
+        let results = ["3 blue, 4 red", "1 red, 2 green, 6 blue", "2 green"]
+            .map(|result| result.parse::<GrabResult>().unwrap());
+        let game = Game::new(1, results.to_vec());
+        let parsed = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+            .parse::<Game>()
+            .unwrap();
+        assert_eq!(game, parsed);
+        assert_ne!(Game::new(2, results.to_vec()), parsed);
+        assert_eq!(parsed.clone(), parsed);
+    }
+
     #[test]
     fn test_game_outcome_possibilities() {
         let game_1 = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
@@ -243,4 +378,102 @@ mod tests {
         let bag = GameBag::minimum_for(&game_1);
         assert_eq!(bag.power(), 48);
     }
+
+    #[test]
+    fn test_grab_result_colors_in_order() {
+        let result = "4 red, 2 green, 3 blue".parse::<GrabResult>().unwrap();
+        assert_eq!(
+            result.colors_in_order(),
+            vec![("blue", 3), ("red", 4), ("green", 2)]
+        );
+    }
+
+    #[test]
+    fn test_grab_result_round_trip() {
+        let result = "3 blue, 4 red".parse::<GrabResult>().unwrap();
+        let formatted = result.to_string();
+        assert_eq!(formatted, "3 blue, 4 red");
+        assert_eq!(formatted.parse::<GrabResult>().unwrap(), result);
+    }
+
+    #[test]
+    fn test_parse_game_bag() {
+        let bag = "12 red, 13 green, 14 blue".parse::<GameBag>().unwrap();
+        assert_eq!(bag.red, 12);
+        assert_eq!(bag.green, 13);
+        assert_eq!(bag.blue, 14);
+    }
+
+    #[test]
+    fn test_game_bag_round_trip() {
+        let bag = GameBag::new(12, 13, 14);
+        let formatted = bag.to_string();
+        assert_eq!(formatted, "12 red, 13 green, 14 blue");
+        assert_eq!(formatted.parse::<GameBag>().unwrap(), bag);
+
+        let partial = "5 red".parse::<GameBag>().unwrap();
+        assert_eq!(partial.to_string().parse::<GameBag>().unwrap(), partial);
+    }
+
+    #[test]
+    fn test_parse_partial_game_bag() {
+        let bag = "5 red".parse::<GameBag>().unwrap();
+        assert_eq!(bag.red, 5);
+        assert_eq!(bag.green, 0);
+        assert_eq!(bag.blue, 0);
+    }
+
+    #[test]
+    fn test_remaining_after() {
+        let bag = GameBag::new(0, 0, 5);
+        let remaining = bag
+            .remaining_after(&"3 blue".parse::<GrabResult>().unwrap())
+            .unwrap();
+        assert_eq!((remaining.red, remaining.green, remaining.blue), (0, 0, 2));
+    }
+
+    #[test]
+    fn test_remaining_after_overdrawn() {
+        let bag = GameBag::new(0, 0, 5);
+        let grab = "6 blue".parse::<GrabResult>().unwrap();
+        assert!(bag.remaining_after(&grab).is_none());
+    }
+
+    #[test]
+    fn test_example_games_sums() {
+        let games = EXAMPLE_INPUT.parse::<Games>().unwrap();
+        assert_eq!(games.len(), 5);
+        assert_eq!(games.possible_ids_sum(&GameBag::new(12, 13, 14)), 8);
+        assert_eq!(games.minimum_bag_power_sum(), 2286);
+    }
+
+    #[test]
+    fn test_scaled_bag() {
+        let bag = GameBag::new(12, 13, 14);
+        let doubled = bag.scale(2);
+        assert_eq!(doubled, GameBag::new(24, 26, 28));
+        assert_eq!(bag.scale(1), bag);
+        assert_eq!(
+            GameBag::new(u32::MAX, 0, 1).scale(2),
+            GameBag::new(u32::MAX, 0, 2)
+        );
+
+        // Games 3 and 4 need more red or blue than the original bag holds.
+        let games = EXAMPLE_INPUT.parse::<Games>().unwrap();
+        assert_eq!(games.possible_ids_sum(&bag), 1 + 2 + 5);
+        assert_eq!(games.possible_ids_sum(&doubled), 1 + 2 + 3 + 4 + 5);
+    }
+
+    #[test]
+    fn test_covering_bag() {
+        let games = EXAMPLE_INPUT
+            .lines()
+            .map(|g| g.parse::<Game>().unwrap())
+            .collect::<Vec<_>>();
+        let bag = GameBag::covering(&games);
+        assert_eq!(bag.red, 20);
+        assert_eq!(bag.green, 13);
+        assert_eq!(bag.blue, 15);
+        assert!(games.iter().all(|g| bag.is_game_possible(g)));
+    }
 }
@@ -1,25 +1,46 @@
-use std::cmp::{max, min};
-use std::ops::Range;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+use core::ops::{Range, Sub};
 
 pub trait Span {
+    type Length;
+
+    /// The number of values covered, or zero if the span is empty.
+    fn length(&self) -> Self::Length;
     fn is_left_adjacent_to(&self, other: &Self) -> bool;
     fn is_right_adjacent_to(&self, other: &Self) -> bool;
     fn overlaps(&self, other: &Self) -> bool;
     fn intersection(&self, other: &Self) -> Option<Self>
     where
         Self: Sized;
+    fn clamp(&self, bounds: &Self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        self.intersection(bounds)
+    }
     fn is_adjacent_to(&self, other: &Self) -> bool {
         self.is_left_adjacent_to(other) || self.is_right_adjacent_to(other)
     }
-    fn overlaps_or_is_adjacent_to(&self, other: &Self) -> bool {
+    fn touches(&self, other: &Self) -> bool {
         self.overlaps(other) || self.is_adjacent_to(other)
     }
+    #[deprecated(note = "use `touches` instead")]
+    fn overlaps_or_is_adjacent_to(&self, other: &Self) -> bool {
+        self.touches(other)
+    }
 }
 
 impl<T> Span for Range<T>
 where
-    T: Ord + PartialEq + PartialOrd + Copy,
+    T: Ord + PartialEq + PartialOrd + Copy + Sub<Output = T>,
 {
+    type Length = T;
+
+    fn length(&self) -> T {
+        self.end - min(self.start, self.end)
+    }
+
     fn is_left_adjacent_to(&self, other: &Self) -> bool {
         self.end == other.start
     }
@@ -41,10 +62,81 @@ where
     }
 }
 
+/// Sorts `ranges` and merges any that overlap or are adjacent, dropping empty ranges.
+pub fn merge<T>(ranges: impl IntoIterator<Item = Range<T>>) -> Vec<Range<T>>
+where
+    T: Ord + Copy + Sub<Output = T>,
+{
+    let mut ranges = ranges
+        .into_iter()
+        .filter(|range| range.start < range.end)
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if last.touches(&range) => last.end = max(last.end, range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// The ranges in `haystack` that overlap `needle`, in their original order.
+pub fn overlapping<'a, T>(haystack: &'a [Range<T>], needle: &Range<T>) -> Vec<&'a Range<T>>
+where
+    T: Ord + Copy + Sub<Output = T>,
+{
+    haystack
+        .iter()
+        .filter(|range| range.overlaps(needle))
+        .collect()
+}
+
+/// Like `overlapping`, but binary searches `haystack`, which must be sorted and free of overlaps
+/// (such as the components of a schematic line).
+pub fn overlapping_sorted<'a, T>(haystack: &'a [Range<T>], needle: &Range<T>) -> Vec<&'a Range<T>>
+where
+    T: Ord + Copy + Sub<Output = T>,
+{
+    overlapping_sorted_by(haystack, needle, |range| range)
+}
+
+/// Like `overlapping_sorted`, but for items that each cover a span, which `span` extracts.
+pub fn overlapping_sorted_by<'a, I, T>(
+    haystack: &'a [I],
+    needle: &Range<T>,
+    span: impl Fn(&I) -> &Range<T>,
+) -> Vec<&'a I>
+where
+    T: Ord + Copy + Sub<Output = T>,
+{
+    let first = haystack.partition_point(|item| span(item).end <= needle.start);
+    haystack[first..]
+        .iter()
+        .take_while(|item| span(item).start < needle.end)
+        .filter(|item| span(item).overlaps(needle))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_length() {
+        assert_eq!((2..6).length(), 4);
+        assert_eq!((2_u64..6).length(), 4);
+    }
+
+    #[test]
+    fn test_length_empty() {
+        assert_eq!((5..5).length(), 0);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 6_u64..2;
+        assert_eq!(reversed.length(), 0);
+    }
+
     #[test]
     fn test_is_left_adjacent_to() {
         let a = 1..3;
@@ -86,6 +178,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_overlaps_or_is_adjacent_to() {
         let a = 1..3;
         let b = 3..5;
@@ -94,10 +187,95 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_overlaps_or_is_adjacent_to_negative() {
         let a = 1..2;
         let b = 3..5;
         assert!(!a.overlaps_or_is_adjacent_to(&b));
         assert!(!b.overlaps_or_is_adjacent_to(&a));
     }
+
+    #[test]
+    fn test_touches() {
+        assert!((1..3).touches(&(3..5)));
+        assert!((3..5).touches(&(1..3)));
+        assert!((1..4).touches(&(3..5)));
+        assert!(!(1..2).touches(&(3..5)));
+        assert!(!(3..5).touches(&(1..2)));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_touches_matches_overlaps_or_is_adjacent_to() {
+        let cases = [
+            (1..3, 3..5),
+            (1..4, 3..5),
+            (1..2, 3..5),
+            (3..5, 1..3),
+            (2..2, 2..4),
+        ];
+        for (a, b) in cases {
+            assert_eq!(a.touches(&b), a.overlaps_or_is_adjacent_to(&b));
+            assert_eq!(b.touches(&a), b.overlaps_or_is_adjacent_to(&a));
+        }
+    }
+
+    #[test]
+    fn test_clamp_within_bounds() {
+        assert_eq!((1..100).clamp(&(10..20)), Some(10..20));
+        assert_eq!((15..100).clamp(&(10..20)), Some(15..20));
+    }
+
+    #[test]
+    fn test_clamp_disjoint() {
+        assert_eq!((1..100).clamp(&(200..300)), None);
+    }
+
+    #[test]
+    fn test_merge() {
+        let merged = merge([10..12, 1..3, 2..5, 5..7, 9..9]);
+        assert_eq!(merged, vec![1..7, 10..12]);
+    }
+
+    #[test]
+    fn test_overlapping_sorted_matches_unsorted() {
+        let haystack = [0..3, 3..4, 4..7, 7..7, 7..8, 10..12];
+        let needles = [0..1, 2..5, 3..4, 7..10, 8..10, 11..20, 20..30, 5..5];
+        for needle in needles {
+            assert_eq!(
+                overlapping_sorted(&haystack, &needle),
+                overlapping(&haystack, &needle),
+                "needle {:?}",
+                needle
+            );
+        }
+        assert_eq!(
+            overlapping(&haystack, &(2..5)),
+            vec![&(0..3), &(3..4), &(4..7)]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_sorted_by() {
+        let haystack = [(0..3, 'a'), (3..4, 'b'), (4..7, 'c'), (10..12, 'd')];
+        let labels = |needle: Range<i32>| {
+            overlapping_sorted_by(&haystack, &needle, |(span, _)| span)
+                .into_iter()
+                .map(|(_, label)| *label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(2..5), vec!['a', 'b', 'c']);
+        assert_eq!(labels(7..10), vec![]);
+        assert_eq!(labels(11..20), vec!['d']);
+    }
+
+    #[test]
+    fn test_overlapping_unsorted() {
+        let haystack = [10..12, 0..3, 5..9];
+        assert_eq!(
+            overlapping(&haystack, &(2..11)),
+            vec![&(10..12), &(0..3), &(5..9)]
+        );
+        assert!(overlapping(&haystack, &(3..5)).is_empty());
+    }
 }
@@ -0,0 +1,211 @@
+use crate::io::Source;
+use crate::{Solution, Solver};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Timings { samples }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.samples.first().copied()
+    }
+
+    pub fn median(&self) -> Option<Duration> {
+        self.samples.get(self.samples.len() / 2).copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.last().copied()
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (self.min(), self.median(), self.max()) {
+            (Some(min), Some(median), Some(max)) => write!(
+                f,
+                "{} run(s): min {:?}, median {:?}, max {:?}",
+                self.len(),
+                min,
+                median,
+                max
+            ),
+            _ => write!(f, "no runs"),
+        }
+    }
+}
+
+/// Solves `repeat` times, timing only the solver. The input is read once up front, so sources
+/// that can only be read once, such as stdin, can still be repeated.
+pub fn time_solve<T, S>(
+    solver: &S,
+    input: &Source,
+    repeat: usize,
+) -> Result<(Solution<T>, Timings), S::Err>
+where
+    T: Copy + Display,
+    S: Solver<T>,
+{
+    time_solve_str(solver, &input.read_string()?, repeat)
+}
+
+/// Like `time_solve`, but reads the input from any reader.
+pub fn time_solve_reader<T, S>(
+    solver: &S,
+    reader: &mut dyn Read,
+    repeat: usize,
+) -> Result<(Solution<T>, Timings), S::Err>
+where
+    T: Copy + Display,
+    S: Solver<T>,
+{
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    time_solve_str(solver, &input, repeat)
+}
+
+fn time_solve_str<T, S>(
+    solver: &S,
+    input: &str,
+    repeat: usize,
+) -> Result<(Solution<T>, Timings), S::Err>
+where
+    T: Copy + Display,
+    S: Solver<T>,
+{
+    let start = Instant::now();
+    let solution = solver.solve_str(input)?;
+    let mut samples = vec![start.elapsed()];
+
+    // The first run's solution is kept; later runs only contribute samples.
+    for _ in 1..repeat {
+        let start = Instant::now();
+        solver.solve_str(input)?;
+        samples.push(start.elapsed());
+    }
+
+    Ok((solution, Timings::new(samples)))
+}
+
+#[derive(Debug, Error)]
+pub enum TimeoutError<E> {
+    #[error("Solve timed out after {0:?}")]
+    TimedOut(Duration),
+    #[error(transparent)]
+    Solve(E),
+}
+
+/// Solves on a worker thread, giving up after `timeout`. A timed out solve is left running in the
+/// background, so callers are expected to exit soon after.
+pub fn solve_with_timeout<T, S>(
+    solver: S,
+    input: Source,
+    timeout: Duration,
+) -> Result<Solution<T>, TimeoutError<S::Err>>
+where
+    T: Copy + Display + Send + 'static,
+    S: Solver<T> + Send + 'static,
+    S::Err: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if we already timed out, in which case the result is unwanted.
+        let _ = sender.send(solver.solve(&input));
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result.map_err(TimeoutError::Solve),
+        Err(_) => Err(TimeoutError::TimedOut(timeout)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day1::{CalibrationSolver, CalibrationSolverError};
+
+    #[test]
+    fn test_timings_stats() {
+        let timings = Timings::new(vec![
+            Duration::from_millis(3),
+            Duration::from_millis(1),
+            Duration::from_millis(2),
+        ]);
+        assert_eq!(timings.min(), Some(Duration::from_millis(1)));
+        assert_eq!(timings.median(), Some(Duration::from_millis(2)));
+        assert_eq!(timings.max(), Some(Duration::from_millis(3)));
+    }
+
+    #[test]
+    fn test_time_solve_repeat() -> Result<(), CalibrationSolverError> {
+        let input = Source::try_from("inputs/day-1.txt")?;
+        let (solution, timings) = time_solve(&CalibrationSolver, &input, 3)?;
+        assert_eq!(solution.part1(), 55029);
+        assert_eq!(timings.len(), 3);
+        assert!(timings.min() <= timings.median());
+        assert!(timings.median() <= timings.max());
+        assert!(timings.to_string().starts_with("3 run(s): min "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_solve_reader_reads_once() -> Result<(), CalibrationSolverError> {
+        let input = std::fs::read("inputs/day-1.txt")?;
+        // A byte slice is drained by the first read, like stdin.
+        let mut reader = input.as_slice();
+        let (solution, timings) = time_solve_reader(&CalibrationSolver, &mut reader, 3)?;
+        assert_eq!(solution.part1(), 55029);
+        assert_eq!(timings.len(), 3);
+        Ok(())
+    }
+
+    struct SlowSolver(Duration);
+
+    impl Solver<i64> for SlowSolver {
+        type Err = std::io::Error;
+
+        fn solve_str(&self, _input: &str) -> Result<Solution<i64>, Self::Err> {
+            thread::sleep(self.0);
+            Ok(Solution::partial(1))
+        }
+    }
+
+    #[test]
+    fn test_solve_with_timeout_gives_up() {
+        let input = Source::Memory(String::new());
+        let result = solve_with_timeout(
+            SlowSolver(Duration::from_secs(5)),
+            input,
+            Duration::from_millis(10),
+        );
+        assert!(matches!(result, Err(TimeoutError::TimedOut(_))));
+    }
+
+    #[test]
+    fn test_solve_with_timeout_returns_solution() {
+        let input = Source::try_from("inputs/day-1.txt").unwrap();
+        let solution =
+            solve_with_timeout(CalibrationSolver, input, Duration::from_secs(60)).unwrap();
+        assert_eq!(solution.part1(), 55029);
+    }
+}
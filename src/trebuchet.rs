@@ -1,14 +1,30 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 use thiserror::Error;
 
 #[derive(Debug)]
-struct CalibrationValueReader {
+pub struct CalibrationValueReader {
     spelled_out_digits: Vec<(&'static str, u8)>,
 }
 
+/// A digit picked out of a calibration line, and where it was found.
+#[derive(Debug, PartialEq)]
+pub struct RecoveredDigit {
+    pub digit: u8,
+    pub position: usize,
+    pub from_word: bool,
+}
+
 #[derive(Debug, Error, PartialEq)]
 pub enum CalibrationValueError {
     #[error("No digits found")]
     NoDigitsFound,
+    #[error("Empty input")]
+    EmptyInput,
 }
 
 impl CalibrationValueReader {
@@ -44,6 +60,100 @@ impl CalibrationValueReader {
         Self::try_join_two_digits(first_digit, second_digit)
     }
 
+    /// Like `recover_from_str_v2`, but only spelled-out digits count; numeric characters are
+    /// ignored.
+    pub fn recover_words_only(&self, s: &str) -> Result<i32, CalibrationValueError> {
+        let word_at = |(position, _)| self.spelled_out_digit_at_start(&s[position..]);
+        let first_digit = s.char_indices().find_map(word_at);
+        // As in `recover_with_positions`, the word starting last is also the one ending last.
+        let second_digit = s.char_indices().rev().find_map(word_at);
+        Self::try_join_two_digits(first_digit, second_digit)
+    }
+
+    pub fn recover_all(&self, input: &str) -> Result<Vec<i32>, CalibrationValueError> {
+        non_empty_lines(input)?
+            .map(|line| self.recover_from_str(line))
+            .collect()
+    }
+
+    pub fn recover_all_v2(&self, input: &str) -> Result<Vec<i32>, CalibrationValueError> {
+        non_empty_lines(input)?
+            .map(|line| self.recover_from_str_v2(line))
+            .collect()
+    }
+
+    /// Finds the same first and last digits as `recover_all_v2`, along with their byte positions
+    /// and whether each was spelled out.
+    pub fn recover_with_positions(
+        &self,
+        s: &str,
+    ) -> Result<(RecoveredDigit, RecoveredDigit), CalibrationValueError> {
+        // No digit word contains another, so the word starting last is also the one ending last.
+        let mut digits = s
+            .char_indices()
+            .filter_map(|(position, _)| self.recovered_digit_at(s, position));
+        let first = digits.next().ok_or(CalibrationValueError::NoDigitsFound)?;
+        let last = s
+            .char_indices()
+            .rev()
+            .find_map(|(position, _)| self.recovered_digit_at(s, position))
+            .ok_or(CalibrationValueError::NoDigitsFound)?;
+        Ok((first, last))
+    }
+
+    fn recovered_digit_at(&self, s: &str, position: usize) -> Option<RecoveredDigit> {
+        let rest = &s[position..];
+        if let Some(digit) = rest.chars().next()?.to_digit(10) {
+            return Some(RecoveredDigit {
+                digit: digit as u8,
+                position,
+                from_word: false,
+            });
+        }
+        self.spelled_out_digit_at_start(rest)
+            .map(|digit| RecoveredDigit {
+                digit,
+                position,
+                from_word: true,
+            })
+    }
+
+    /// Describes, line by line, which digits were chosen for the spelled-out calibration values.
+    pub fn explain(&self, input: &str) -> Result<String, CalibrationValueError> {
+        let mut explanation = String::new();
+        for line in input.lines() {
+            let (first, last) = self.recover_with_positions(line)?;
+            let value = Self::try_join_two_digits(Some(first.digit), Some(last.digit))?;
+            explanation.push_str(&format!(
+                "{}: first {}, last {} = {}\n",
+                line,
+                describe_digit(&first),
+                describe_digit(&last),
+                value
+            ));
+        }
+        Ok(explanation)
+    }
+
+    /// Counts every occurrence of each spelled-out digit, including ones that overlap another word
+    /// (`eightwo` counts both `eight` and `two`). Words that never appear are omitted.
+    #[cfg(feature = "std")]
+    pub fn word_frequencies(&self, input: &str) -> HashMap<&'static str, usize> {
+        let mut frequencies = HashMap::new();
+        for line in input.lines() {
+            for start in 0..line.len() {
+                if let Some(rest) = line.get(start..) {
+                    for (spelled_out, _) in &self.spelled_out_digits {
+                        if rest.starts_with(spelled_out) {
+                            *frequencies.entry(*spelled_out).or_insert(0) += 1;
+                        }
+                    }
+                }
+            }
+        }
+        frequencies
+    }
+
     fn spelled_out_digit_at_start(&self, value: &str) -> Option<u8> {
         for (spelled_out, digit) in &self.spelled_out_digits {
             if value.starts_with(spelled_out) {
@@ -95,6 +205,21 @@ impl CalibrationValueReader {
     }
 }
 
+/// The lines of `input`, or `EmptyInput` if there are none, so that an empty input is not
+/// mistaken for one that sums to zero.
+fn non_empty_lines(input: &str) -> Result<core::str::Lines<'_>, CalibrationValueError> {
+    if input.lines().next().is_none() {
+        Err(CalibrationValueError::EmptyInput)
+    } else {
+        Ok(input.lines())
+    }
+}
+
+fn describe_digit(recovered: &RecoveredDigit) -> String {
+    let kind = if recovered.from_word { "word" } else { "digit" };
+    format!("{} ({} at {})", recovered.digit, kind, recovered.position)
+}
+
 impl Default for CalibrationValueReader {
     fn default() -> Self {
         Self {
@@ -115,24 +240,14 @@ impl Default for CalibrationValueReader {
 
 pub fn sum_calibration_values(input: &str) -> Result<i32, CalibrationValueError> {
     let reader = CalibrationValueReader::default();
-    input
-        .lines()
-        .map(|line| {
-            let value = reader.recover_from_str(line)?;
-            Ok(value)
-        })
-        .sum()
+    reader.recover_all(input).map(|values| values.iter().sum())
 }
 
 pub fn sum_calibration_values_v2(input: &str) -> Result<i32, CalibrationValueError> {
     let reader = CalibrationValueReader::default();
-    input
-        .lines()
-        .map(|line| {
-            let value = reader.recover_from_str_v2(line)?;
-            Ok(value)
-        })
-        .sum()
+    reader
+        .recover_all_v2(input)
+        .map(|values| values.iter().sum())
 }
 
 #[cfg(test)]
@@ -247,6 +362,18 @@ pub mod test {
         assert_eq!(result, 76);
     }
 
+    #[test]
+    fn test_recover_words_only() {
+        let reader = CalibrationValueReader::default();
+        assert_eq!(reader.recover_words_only("1two3four5"), Ok(24));
+        assert_eq!(reader.recover_words_only("xtwone3four"), Ok(24));
+        assert_eq!(reader.recover_words_only("7pqrstsixteen"), Ok(66));
+        assert_eq!(
+            reader.recover_words_only("1abc2"),
+            Err(CalibrationValueError::NoDigitsFound)
+        );
+    }
+
     #[test]
     fn test_calibration_value_parse_missing_digits() {
         let input = "one";
@@ -266,4 +393,79 @@ pub mod test {
             assert_eq!(err, CalibrationValueError::NoDigitsFound);
         }
     }
+
+    #[test]
+    fn test_sum_calibration_values_empty_input() {
+        assert_eq!(
+            sum_calibration_values(""),
+            Err(CalibrationValueError::EmptyInput)
+        );
+        assert_eq!(
+            sum_calibration_values_v2(""),
+            Err(CalibrationValueError::EmptyInput)
+        );
+        assert_eq!(
+            sum_calibration_values("abc"),
+            Err(CalibrationValueError::NoDigitsFound)
+        );
+    }
+
+    #[test]
+    fn test_recover_all() {
+        let reader = CalibrationValueReader::default();
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
+        assert_eq!(reader.recover_all(input).unwrap(), vec![12, 38, 15, 77]);
+    }
+
+    #[test]
+    fn test_recover_all_v2() {
+        let reader = CalibrationValueReader::default();
+        let input = "two1nine\neightwothree\nabcone2threexyz";
+        assert_eq!(reader.recover_all_v2(input).unwrap(), vec![29, 83, 13]);
+    }
+
+    #[test]
+    fn test_recover_with_positions() {
+        let reader = CalibrationValueReader::default();
+        let (first, last) = reader.recover_with_positions("xtwone3four").unwrap();
+        assert_eq!(
+            first,
+            RecoveredDigit {
+                digit: 2,
+                position: 1,
+                from_word: true
+            }
+        );
+        assert_eq!(
+            last,
+            RecoveredDigit {
+                digit: 4,
+                position: 7,
+                from_word: true
+            }
+        );
+        let (first, _) = reader.recover_with_positions("7pqrstsixteen").unwrap();
+        assert!(!first.from_word);
+    }
+
+    #[test]
+    fn test_explain_eightwothree() {
+        let reader = CalibrationValueReader::default();
+        assert_eq!(
+            reader.explain("eightwothree").unwrap(),
+            "eightwothree: first 8 (word at 0), last 3 (word at 7) = 83\n"
+        );
+    }
+
+    #[test]
+    fn test_word_frequencies() {
+        let reader = CalibrationValueReader::default();
+        let frequencies = reader.word_frequencies("eightwothree\nabcone2threexyz");
+        assert_eq!(frequencies.get("eight"), Some(&1));
+        assert_eq!(frequencies.get("two"), Some(&1));
+        assert_eq!(frequencies.get("three"), Some(&2));
+        assert_eq!(frequencies.get("one"), Some(&1));
+        assert_eq!(frequencies.get("four"), None);
+        assert_eq!(frequencies.len(), 4);
+    }
 }